@@ -0,0 +1,145 @@
+use std::fmt::{Display, Formatter};
+
+use matrices::{Report, ReportStyle, ReportTable};
+use ndarray::{ArrayView1, ArrayView2};
+use prettytable::{Row, Table};
+
+use crate::norm::{max_abs, MatrixNorm};
+use crate::refinement::{residual_vector, Residual};
+use crate::TABLE_STYLE;
+
+/// Backward errors of a computed solution `x` of `Ax = b`.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct BackwardErrors {
+    normwise: f64,
+    componentwise: f64,
+}
+
+impl BackwardErrors {
+    pub fn new(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, x: ArrayView1<f64>) -> Self {
+        Self {
+            normwise: normwise_backward_error(matrix, vector, x),
+            componentwise: componentwise_backward_error(matrix, vector, x),
+        }
+    }
+
+    pub fn normwise(&self) -> f64 {
+        self.normwise
+    }
+
+    pub fn componentwise(&self) -> f64 {
+        self.componentwise
+    }
+
+    pub fn backward_table(&self) -> Table {
+        self.report_table(&TABLE_STYLE).pretty()
+    }
+}
+
+impl Report for BackwardErrors {
+    fn report_table(&self, style: &ReportStyle) -> ReportTable {
+        let mut table = ReportTable::new(&[
+            style.text("нормированная", "normwise"),
+            style.text("покомпонентная", "componentwise"),
+        ]);
+        table.add_row(vec![
+            style.scientific(self.normwise),
+            style.scientific(self.componentwise),
+        ]);
+        table
+    }
+
+    fn describe(&self, style: &ReportStyle) -> String {
+        format!(
+            "{}: {}\n{}: {}",
+            style.text("Нормированная обратная ошибка", "Normwise backward error"),
+            style.scientific(self.normwise),
+            style.text(
+                "Покомпонентная обратная ошибка",
+                "Componentwise backward error"
+            ),
+            style.scientific(self.componentwise)
+        )
+    }
+}
+
+impl Display for BackwardErrors {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(&ReportStyle::default()))
+    }
+}
+
+/// The row of the report table in the default style.
+impl From<BackwardErrors> for Row {
+    fn from(value: BackwardErrors) -> Self {
+        value.report_table(&ReportStyle::default()).pretty_row(0)
+    }
+}
+
+/// Rigal–Gaches: `||r||∞ / (||A||∞ ||x||∞ + ||b||∞)`.
+fn normwise_backward_error(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    x: ArrayView1<f64>,
+) -> f64 {
+    let residual = residual_vector(matrix, vector, x, Residual::Compensated);
+    let matrix_norm = MatrixNorm::Infinity.of(matrix);
+
+    max_abs(residual.view()) / (matrix_norm * max_abs(x) + max_abs(vector))
+}
+
+/// Oettli–Prager: `max_i |r_i| / (|A||x| + |b|)_i`.
+fn componentwise_backward_error(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    x: ArrayView1<f64>,
+) -> f64 {
+    let residual = residual_vector(matrix, vector, x, Residual::Compensated);
+    let bound = matrix.mapv(f64::abs).dot(&x.mapv(f64::abs)) + vector.mapv(f64::abs);
+
+    residual
+        .iter()
+        .zip(bound.iter())
+        .map(|(r, &bound)| {
+            if *r == 0.0 {
+                0.0
+            } else if bound == 0.0 {
+                f64::INFINITY
+            } else {
+                r.abs() / bound
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+    use ndarray::array;
+
+    use crate::backward::BackwardErrors;
+
+    #[test]
+    fn exact_solution_has_zero_backward_error() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+        let vector = array![3.0, 7.0];
+        let x = array![1.0, 1.0];
+
+        let errors = BackwardErrors::new(matrix.view(), vector.view(), x.view());
+        assert_eq!(errors.normwise(), 0.0);
+        assert_eq!(errors.componentwise(), 0.0);
+    }
+
+    #[test]
+    fn backward_errors_of_perturbed_solution() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+        let vector = array![3.0, 7.0];
+        let x = array![1.0, 1.5];
+
+        // r = [-1, -2], ||A||∞ = 7, ||x||∞ = 1.5, ||b||∞ = 7
+        // |A||x| + |b| = [7, 16]
+        let errors = BackwardErrors::new(matrix.view(), vector.view(), x.view());
+        assert_approx_eq!(errors.normwise(), 2.0 / (7.0 * 1.5 + 7.0), f64::EPSILON);
+        assert_approx_eq!(errors.componentwise(), 1.0 / 7.0, f64::EPSILON);
+    }
+}
@@ -0,0 +1,289 @@
+use std::fmt::{Display, Formatter};
+
+use matrices::{Report, ReportStyle, ReportTable};
+use ndarray::{Array1, ArrayView1, ArrayView2};
+use ndarray_linalg::{c64, Eig, Inverse, Norm};
+use prettytable::{Row, Table};
+
+use crate::error::{check_matrix, ConditionError, Criterion};
+use crate::TABLE_STYLE;
+
+/// Condition numbers with respect to componentwise relative perturbations,
+/// invariant under row scaling of the system.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct ComponentwiseConditions {
+    skeel: f64,
+    skeel_solution: f64,
+    mixed: f64,
+    componentwise: f64,
+}
+
+impl ComponentwiseConditions {
+    pub fn new(matrix: ArrayView2<f64>, vector: ArrayView1<f64>) -> Result<Self, ConditionError> {
+        check_matrix(matrix, Criterion::Componentwise)?;
+        let inverse = matrix
+            .inv()
+            .map_err(ConditionError::factorization(Criterion::Componentwise))?;
+        let x = inverse.dot(&vector);
+        let inverse = inverse.mapv(f64::abs);
+        let matrix = matrix.mapv(f64::abs);
+        let x_abs = x.mapv(f64::abs);
+        let x_norm = x_abs.norm_max();
+
+        let product = inverse.dot(&matrix);
+        let skeel = product
+            .rows()
+            .into_iter()
+            .map(|row| row.sum())
+            .fold(0.0, f64::max);
+        let skeel_solution = product.dot(&x_abs).norm_max() / x_norm;
+
+        let sensitivity = inverse.dot(&(matrix.dot(&x_abs) + vector.mapv(f64::abs)));
+        let mixed = sensitivity.norm_max() / x_norm;
+        let componentwise = componentwise_ratio(sensitivity, x_abs);
+
+        Ok(Self {
+            skeel,
+            skeel_solution,
+            mixed,
+            componentwise,
+        })
+    }
+
+    /// `cond(A) = || |A⁻¹||A| ||∞`.
+    pub fn skeel(&self) -> f64 {
+        self.skeel
+    }
+
+    /// `cond(A, x) = || |A⁻¹||A||x| ||∞ / ||x||∞`.
+    pub fn skeel_solution(&self) -> f64 {
+        self.skeel_solution
+    }
+
+    /// `|| |A⁻¹|(|A||x| + |b|) ||∞ / ||x||∞`, relative perturbations of both `A` and `b`.
+    pub fn mixed(&self) -> f64 {
+        self.mixed
+    }
+
+    /// `max_i (|A⁻¹|(|A||x| + |b|))_i / |x_i|`, the error measured in every component of `x`.
+    pub fn componentwise(&self) -> f64 {
+        self.componentwise
+    }
+
+    pub fn condition_table(&self) -> Table {
+        self.report_table(&TABLE_STYLE).pretty()
+    }
+}
+
+impl Report for ComponentwiseConditions {
+    fn report_table(&self, style: &ReportStyle) -> ReportTable {
+        let mut table = ReportTable::new(&[
+            style.text("Скил", "skeel"),
+            style.text("Скил (x)", "skeel (x)"),
+            style.text("смешанное", "mixed"),
+            style.text("покомпонентное", "componentwise"),
+        ]);
+        table.add_row(vec![
+            style.number(self.skeel),
+            style.number(self.skeel_solution),
+            style.number(self.mixed),
+            style.number(self.componentwise),
+        ]);
+        table
+    }
+
+    fn describe(&self, style: &ReportStyle) -> String {
+        format!(
+            "{}: {}\n{}: {}\n{}: {}\n{}: {}",
+            style.text("Число обусловленности Скила", "Skeel condition number"),
+            style.number(self.skeel),
+            style.text(
+                "Число обусловленности Скила для решения",
+                "Skeel condition number of the solution"
+            ),
+            style.number(self.skeel_solution),
+            style.text("Смешанное число обусловленности", "Mixed condition number"),
+            style.number(self.mixed),
+            style.text(
+                "Покомпонентное число обусловленности",
+                "Componentwise condition number"
+            ),
+            style.number(self.componentwise)
+        )
+    }
+}
+
+fn componentwise_ratio(sensitivity: Array1<f64>, x_abs: Array1<f64>) -> f64 {
+    sensitivity
+        .iter()
+        .zip(x_abs.iter())
+        .map(|(&s, &x)| {
+            if s == 0.0 {
+                0.0
+            } else if x == 0.0 {
+                f64::INFINITY
+            } else {
+                s / x
+            }
+        })
+        .fold(0.0, f64::max)
+}
+
+impl Display for ComponentwiseConditions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(&ReportStyle::default()))
+    }
+}
+
+/// The row of the report table in the default style.
+impl From<ComponentwiseConditions> for Row {
+    fn from(value: ComponentwiseConditions) -> Self {
+        value.report_table(&ReportStyle::default()).pretty_row(0)
+    }
+}
+
+/// Condition numbers `||x|| ||y|| / |yᴴx|` of simple eigenvalues,
+/// `x` and `y` being the right and left eigenvectors.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EigenvalueConditions {
+    eigenvalues: Vec<c64>,
+    conditions: Vec<f64>,
+}
+
+impl EigenvalueConditions {
+    pub fn new(matrix: ArrayView2<f64>) -> Result<Self, ConditionError> {
+        check_matrix(matrix, Criterion::Eigenvalue)?;
+        let (eigenvalues, right) = matrix
+            .eig()
+            .map_err(ConditionError::lapack(Criterion::Eigenvalue))?;
+        // left eigenvectors of A are conjugated right eigenvectors of Aᵀ
+        let (transposed_eigenvalues, left) = matrix
+            .t()
+            .eig()
+            .map_err(ConditionError::lapack(Criterion::Eigenvalue))?;
+
+        let mut used = vec![false; eigenvalues.len()];
+        let conditions = eigenvalues
+            .iter()
+            .enumerate()
+            .map(|(i, &eigenvalue)| {
+                let j = (0..transposed_eigenvalues.len())
+                    .filter(|&j| !used[j])
+                    .min_by(|&a, &b| {
+                        let a = (transposed_eigenvalues[a] - eigenvalue).norm();
+                        let b = (transposed_eigenvalues[b] - eigenvalue).norm();
+                        a.total_cmp(&b)
+                    })
+                    .unwrap();
+                used[j] = true;
+
+                let x = right.column(i);
+                let y = left.column(j);
+                let product = x.iter().zip(y.iter()).map(|(a, b)| a * b).sum::<c64>();
+                x.norm_l2() * y.norm_l2() / product.norm()
+            })
+            .collect();
+
+        Ok(Self {
+            eigenvalues: eigenvalues.to_vec(),
+            conditions,
+        })
+    }
+
+    pub fn eigenvalues(&self) -> &[c64] {
+        &self.eigenvalues
+    }
+
+    pub fn conditions(&self) -> &[f64] {
+        &self.conditions
+    }
+
+    pub fn condition_table(&self) -> Table {
+        self.report_table(&TABLE_STYLE).pretty()
+    }
+}
+
+impl Report for EigenvalueConditions {
+    fn report_table(&self, style: &ReportStyle) -> ReportTable {
+        let mut table = ReportTable::new(&[
+            style.text("собственное число", "eigenvalue"),
+            style.text("обусловленность", "condition"),
+        ]);
+        for (eigenvalue, &condition) in self.eigenvalues.iter().zip(self.conditions.iter()) {
+            table.add_row(vec![
+                format!("{eigenvalue:.*}", style.precision),
+                style.number(condition),
+            ]);
+        }
+        table
+    }
+
+    fn describe(&self, style: &ReportStyle) -> String {
+        self.eigenvalues
+            .iter()
+            .zip(self.conditions.iter())
+            .map(|(eigenvalue, &condition)| {
+                format!(
+                    "{} {eigenvalue:.*}: {}\n",
+                    style.text(
+                        "Число обусловленности собственного числа",
+                        "Condition number of eigenvalue"
+                    ),
+                    style.precision,
+                    style.number(condition)
+                )
+            })
+            .collect::<Vec<_>>()
+            .concat()
+    }
+}
+
+impl Display for EigenvalueConditions {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.describe(&ReportStyle::default()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+    use ndarray::array;
+
+    use crate::componentwise::{ComponentwiseConditions, EigenvalueConditions};
+
+    #[test]
+    fn skeel_is_invariant_under_row_scaling() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+        let scaled = array![[1e8, 2e8], [3.0, 4.0]];
+        let vector = array![1.0, 1.0];
+
+        // |A⁻¹||A| = [[5, 8], [3, 5]]
+        let conditions = ComponentwiseConditions::new(matrix.view(), vector.view()).unwrap();
+        let scaled_conditions = ComponentwiseConditions::new(scaled.view(), vector.view()).unwrap();
+        assert_approx_eq!(conditions.skeel(), 13.0, 1e-12);
+        assert_approx_eq!(scaled_conditions.skeel(), 13.0, 1e-6);
+        assert!(conditions.skeel_solution() <= conditions.skeel());
+        assert!(conditions.mixed() <= conditions.componentwise());
+    }
+
+    #[test]
+    fn eigenvalue_conditions_of_triangular_matrix() {
+        let t: f64 = 10.0;
+        let matrix = array![[1.0, t], [0.0, 2.0]];
+
+        let conditions = EigenvalueConditions::new(matrix.view()).unwrap();
+        for &condition in conditions.conditions() {
+            assert_approx_eq!(condition, (1.0 + t * t).sqrt(), 1e-10);
+        }
+    }
+
+    #[test]
+    fn symmetric_eigenvalues_are_perfectly_conditioned() {
+        let matrix = array![[2.0, 1.0, 0.0], [1.0, 3.0, 1.0], [0.0, 1.0, 4.0]];
+
+        let conditions = EigenvalueConditions::new(matrix.view()).unwrap();
+        for &condition in conditions.conditions() {
+            assert_approx_eq!(condition, 1.0, 1e-10);
+        }
+    }
+}
@@ -1,62 +1,284 @@
-use std::error::Error;
+use std::borrow::Cow;
 use std::fmt::{Display, Formatter};
 
-use ndarray::{ArrayView2, Axis};
-use ndarray_linalg::{Determinant, Inverse, Norm};
-use prettytable::{row, Row, Table};
+use matrices::{Report, ReportStyle, ReportTable};
+use ndarray::{Array2, ArrayView2, Axis};
+use ndarray_linalg::error::LinalgError;
+use ndarray_linalg::{Determinant, Factorize, Inverse, Norm};
+use prettytable::{Row, Table};
+
+use crate::error::{check_matrix, ConditionError, Criterion};
+use crate::norm::{singular_values, MatrixNorm};
+use crate::TABLE_STYLE;
 
 #[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct ConditionNumbers {
+    norm: MatrixNorm,
     spectre: f64,
     volume: f64,
     angle: f64,
 }
 
 impl ConditionNumbers {
-    pub fn new(matrix: ArrayView2<f64>) -> Result<Self, Box<dyn Error>> {
-        let spectre = spectre_criterion(matrix)?;
-        let volume = volume_criterion(matrix)?;
-        let angle = angle_criterion(matrix)?;
-        Ok(Self {
-            spectre,
-            volume,
-            angle,
-        })
+    /// Condition numbers with the spectral criterion in the 2-norm.
+    pub fn new(matrix: ArrayView2<f64>) -> Result<Self, ConditionError> {
+        Self::with_norm(matrix, MatrixNorm::Spectral)
+    }
+
+    pub fn with_norm(matrix: ArrayView2<f64>, norm: MatrixNorm) -> Result<Self, ConditionError> {
+        PartialConditionNumbers::new(matrix, norm).complete()
+    }
+
+    /// Condition numbers for every norm in [`MatrixNorm::ALL`],
+    /// the volume and angle criteria do not depend on the norm and are computed once.
+    pub fn all_norms(matrix: ArrayView2<f64>) -> Result<Vec<Self>, ConditionError> {
+        let factors = Factors::new(matrix);
+        let spectres = MatrixNorm::ALL
+            .iter()
+            .map(|&norm| spectre_from(matrix, norm, &factors).map(|spectre| (norm, spectre)))
+            .collect::<Result<Vec<_>, _>>()?;
+        let volume = volume_from(matrix, &factors)?;
+        let angle = angle_from(matrix, &factors)?;
+        Ok(spectres
+            .into_iter()
+            .map(|(norm, spectre)| ConditionNumbers {
+                norm,
+                spectre,
+                volume,
+                angle,
+            })
+            .collect())
+    }
+
+    pub fn norm(&self) -> MatrixNorm {
+        self.norm
+    }
+
+    pub fn spectre(&self) -> f64 {
+        self.spectre
+    }
+
+    pub fn volume(&self) -> f64 {
+        self.volume
+    }
+
+    pub fn angle(&self) -> f64 {
+        self.angle
     }
 
     pub fn condition_table(&self) -> Table {
-        let mut table = Table::new();
-        table.add_row(row!["spectre", "volume", "angle"]);
-        table.add_row((*self).into());
+        Self::comparison_table(&[*self])
+    }
+
+    /// One row per entry, e.g. for the output of [`ConditionNumbers::all_norms`].
+    pub fn comparison_table(numbers: &[ConditionNumbers]) -> Table {
+        Self::comparison_report(numbers, &TABLE_STYLE).pretty()
+    }
+
+    pub fn comparison_report(numbers: &[ConditionNumbers], style: &ReportStyle) -> ReportTable {
+        let mut table = ReportTable::new(&[
+            style.text("норма", "norm"),
+            style.text("спектральное", "spectre"),
+            style.text("объемное", "volume"),
+            style.text("угловое", "angle"),
+        ]);
+        for number in numbers {
+            table.add_row(vec![
+                number.norm.to_string(),
+                style.number(number.spectre),
+                style.number(number.volume),
+                style.number(number.angle),
+            ]);
+        }
         table
     }
 }
 
+impl Report for ConditionNumbers {
+    fn report_table(&self, style: &ReportStyle) -> ReportTable {
+        Self::comparison_report(&[*self], style)
+    }
+
+    fn describe(&self, style: &ReportStyle) -> String {
+        format!(
+            "{} ({} {}): {}\n{}: {}\n{}: {}",
+            style.text(
+                "Спектральное число обусловленности",
+                "Spectral condition number"
+            ),
+            style.text("норма", "norm"),
+            self.norm,
+            style.number(self.spectre),
+            style.text("Объемное число обусловленности", "Volume condition number"),
+            style.number(self.volume),
+            style.text("Угловое число обусловленности", "Angle condition number"),
+            style.number(self.angle)
+        )
+    }
+}
+
 impl Display for ConditionNumbers {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Спектральное число обусловленности: {:.3}\nОбъемное число обусловленности: {:.3}\nУгловое число обусловленности: {:.3}",
-        self.spectre, self.volume, self.angle)
+        write!(f, "{}", self.describe(&ReportStyle::default()))
     }
 }
 
+/// The row of [`ConditionNumbers::condition_table`] in the default style.
 impl From<ConditionNumbers> for Row {
     fn from(value: ConditionNumbers) -> Self {
-        row![
-            format!("{:.3}", value.spectre),
-            format!("{:.3}", value.volume),
-            format!("{:.3}", value.angle)
-        ]
+        value.report_table(&ReportStyle::default()).pretty_row(0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for ConditionNumbers {
+    type Record = ConditionNumbers;
+
+    fn records(&self) -> Vec<ConditionNumbers> {
+        vec![*self]
+    }
+}
+
+/// Every criterion computed on its own,
+/// so that e.g. a zero determinant in the volume criterion does not hide the other two.
+/// The inverse and the determinant come from one LU factorisation.
+#[derive(Debug)]
+pub struct PartialConditionNumbers {
+    pub norm: MatrixNorm,
+    pub spectre: Result<f64, ConditionError>,
+    pub volume: Result<f64, ConditionError>,
+    pub angle: Result<f64, ConditionError>,
+}
+
+impl PartialConditionNumbers {
+    pub fn new(matrix: ArrayView2<f64>, norm: MatrixNorm) -> Self {
+        let factors = Factors::new(matrix);
+        Self {
+            norm,
+            spectre: spectre_from(matrix, norm, &factors),
+            volume: volume_from(matrix, &factors),
+            angle: angle_from(matrix, &factors),
+        }
+    }
+
+    /// All three criteria or the first error in the order spectre, volume, angle.
+    pub fn complete(self) -> Result<ConditionNumbers, ConditionError> {
+        Ok(ConditionNumbers {
+            norm: self.norm,
+            spectre: self.spectre?,
+            volume: self.volume?,
+            angle: self.angle?,
+        })
+    }
+}
+
+/// `A⁻¹` and `det A` of one LU factorisation, shared by the criteria.
+enum Factors {
+    Regular {
+        inverse: Array2<f64>,
+        det: f64,
+    },
+    /// LU met an exactly zero pivot.
+    Singular,
+    /// The matrix is rejected by [`check_matrix`] or LAPACK failed,
+    /// each criterion repeats the computation to report its own error.
+    Invalid,
+}
+
+impl Factors {
+    fn new(matrix: ArrayView2<f64>) -> Self {
+        if check_matrix(matrix, Criterion::Volume).is_err() {
+            return Factors::Invalid;
+        }
+        match matrix.factorize() {
+            Ok(lu) => match (lu.inv(), lu.det()) {
+                (Ok(inverse), Ok(det)) => Factors::Regular { inverse, det },
+                _ => Factors::Invalid,
+            },
+            Err(LinalgError::Lapack(lax::error::Error::LapackComputationalFailure { .. })) => {
+                Factors::Singular
+            }
+            Err(_) => Factors::Invalid,
+        }
+    }
+
+    fn inverse(
+        &self,
+        matrix: ArrayView2<f64>,
+        criterion: Criterion,
+    ) -> Result<Cow<'_, Array2<f64>>, ConditionError> {
+        match self {
+            Factors::Regular { inverse, .. } => Ok(Cow::Borrowed(inverse)),
+            Factors::Singular => Err(ConditionError::Singular { criterion }),
+            Factors::Invalid => {
+                check_matrix(matrix, criterion)?;
+                let inverse = matrix
+                    .inv()
+                    .map_err(ConditionError::factorization(criterion))?;
+                Ok(Cow::Owned(inverse))
+            }
+        }
+    }
+
+    fn det(&self, matrix: ArrayView2<f64>, criterion: Criterion) -> Result<f64, ConditionError> {
+        match self {
+            Factors::Regular { det, .. } => Ok(*det),
+            Factors::Singular => Ok(0.0),
+            Factors::Invalid => {
+                check_matrix(matrix, criterion)?;
+                matrix.det().map_err(ConditionError::lapack(criterion))
+            }
+        }
+    }
+}
+
+pub(crate) fn spectre_criterion(
+    matrix: ArrayView2<f64>,
+    norm: MatrixNorm,
+) -> Result<f64, ConditionError> {
+    if norm == MatrixNorm::Spectral {
+        return spectral_condition(matrix);
+    }
+    spectre_from(matrix, norm, &Factors::new(matrix))
+}
+
+fn spectre_from(
+    matrix: ArrayView2<f64>,
+    norm: MatrixNorm,
+    factors: &Factors,
+) -> Result<f64, ConditionError> {
+    if norm == MatrixNorm::Spectral {
+        return spectral_condition(matrix);
     }
+    check_matrix(matrix, Criterion::Spectre)?;
+    let inverse = factors.inverse(matrix, Criterion::Spectre)?;
+    Ok(norm.of(matrix) * norm.of(inverse.view()))
 }
 
-fn spectre_criterion(matrix: ArrayView2<f64>) -> Result<f64, Box<dyn Error>> {
-    let inverse = matrix.inv()?;
-    let spectre = matrix.norm() * inverse.view().norm();
-    Ok(spectre)
+/// `σ_1 / σ_n`.
+fn spectral_condition(matrix: ArrayView2<f64>) -> Result<f64, ConditionError> {
+    check_matrix(matrix, Criterion::Spectre)?;
+    let values = singular_values(matrix).ok_or(ConditionError::NotConverged {
+        criterion: Criterion::Spectre,
+    })?;
+    let smallest = values[values.len() - 1];
+    if smallest == 0.0 {
+        return Err(ConditionError::Singular {
+            criterion: Criterion::Spectre,
+        });
+    }
+    Ok(values[0] / smallest)
 }
 
-fn volume_criterion(matrix: ArrayView2<f64>) -> Result<f64, Box<dyn Error>> {
-    let det = matrix.det()?;
+fn volume_from(matrix: ArrayView2<f64>, factors: &Factors) -> Result<f64, ConditionError> {
+    check_matrix(matrix, Criterion::Volume)?;
+    let det = factors.det(matrix, Criterion::Volume)?;
+    if det == 0.0 {
+        return Err(ConditionError::Singular {
+            criterion: Criterion::Volume,
+        });
+    }
     Ok(matrix
         .axis_iter(Axis(0))
         .map(|row| row.iter().map(|a| a * a).sum::<f64>().sqrt())
@@ -64,27 +286,39 @@ fn volume_criterion(matrix: ArrayView2<f64>) -> Result<f64, Box<dyn Error>> {
         / det.abs())
 }
 
-fn angle_criterion(matrix: ArrayView2<f64>) -> Result<f64, Box<dyn Error>> {
-    let inverse = matrix.inv()?;
-    matrix
+fn angle_from(matrix: ArrayView2<f64>, factors: &Factors) -> Result<f64, ConditionError> {
+    check_matrix(matrix, Criterion::Angle)?;
+    let inverse = factors.inverse(matrix, Criterion::Angle)?;
+    Ok(matrix
         .axis_iter(Axis(0))
         .zip(inverse.axis_iter(Axis(0)))
         .map(|(row, inv)| row.norm() * inv.norm())
-        .max_by(|a, b| a.total_cmp(b))
-        .ok_or(
-            "angle criterion - something went wrong... probably NaN found"
-                .to_string()
-                .into(),
-        )
+        .fold(0.0, f64::max))
 }
 
 #[cfg(test)]
 mod tests {
     use assert_approx_eq::assert_approx_eq;
-    use ndarray::array;
+    use ndarray::{array, Array2, ArrayView2};
     use ndarray_linalg::Determinant;
 
-    use crate::condition::{angle_criterion, volume_criterion};
+    use matrices::{Language, Report, ReportStyle, TableFormat};
+    use prettytable::Row;
+
+    use crate::condition::{
+        angle_from, spectre_criterion, volume_from, ConditionNumbers, Factors,
+        PartialConditionNumbers,
+    };
+    use crate::error::{ConditionError, Criterion};
+    use crate::norm::MatrixNorm;
+
+    fn volume_criterion(matrix: ArrayView2<f64>) -> Result<f64, ConditionError> {
+        volume_from(matrix, &Factors::new(matrix))
+    }
+
+    fn angle_criterion(matrix: ArrayView2<f64>) -> Result<f64, ConditionError> {
+        angle_from(matrix, &Factors::new(matrix))
+    }
 
     #[test]
     fn ortega_test() {
@@ -104,4 +338,137 @@ mod tests {
         let angle = angle_criterion(matrix.view()).unwrap();
         assert_approx_eq!(angle, 5.0 * 2.5_f64.sqrt(), f64::EPSILON)
     }
+
+    #[test]
+    fn spectre_in_different_norms() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+        let _inverse = array![[-2.0, 1.0], [1.5, -0.5]];
+
+        let one = spectre_criterion(matrix.view(), MatrixNorm::One).unwrap();
+        let infinity = spectre_criterion(matrix.view(), MatrixNorm::Infinity).unwrap();
+        let frobenius = spectre_criterion(matrix.view(), MatrixNorm::Frobenius).unwrap();
+        let spectral = spectre_criterion(matrix.view(), MatrixNorm::Spectral).unwrap();
+
+        assert_approx_eq!(one, 6.0 * 3.5, 1e-12);
+        assert_approx_eq!(infinity, 7.0 * 3.0, 1e-12);
+        assert_approx_eq!(frobenius, 30.0_f64.sqrt() * 7.5_f64.sqrt(), 1e-12);
+        // σ² are the roots of λ² - 30λ + 4
+        let root = 221.0_f64.sqrt();
+        assert_approx_eq!(spectral, ((15.0 + root) / (15.0 - root)).sqrt(), 1e-10);
+    }
+
+    #[test]
+    fn all_norms_match_single_norms() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+
+        let numbers = ConditionNumbers::all_norms(matrix.view()).unwrap();
+        assert_eq!(numbers.len(), MatrixNorm::ALL.len());
+        for (numbers, norm) in numbers.iter().zip(MatrixNorm::ALL) {
+            assert_eq!(
+                *numbers,
+                ConditionNumbers::with_norm(matrix.view(), norm).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn zero_determinant_keeps_other_criteria() {
+        // det = 1e-360 underflows, the inverse 1e120 I is representable
+        let matrix = array![[1e-120, 0.0, 0.0], [0.0, 1e-120, 0.0], [0.0, 0.0, 1e-120]];
+
+        let numbers = PartialConditionNumbers::new(matrix.view(), MatrixNorm::Spectral);
+        assert!(matches!(
+            numbers.volume,
+            Err(ConditionError::Singular {
+                criterion: Criterion::Volume
+            })
+        ));
+        assert_approx_eq!(numbers.spectre.unwrap(), 1.0);
+        assert_approx_eq!(numbers.angle.unwrap(), 1.0);
+
+        let error = ConditionNumbers::new(matrix.view()).unwrap_err();
+        assert_eq!(error.criterion(), Criterion::Volume);
+    }
+
+    #[test]
+    fn invalid_matrices_are_rejected() {
+        let singular = array![[1.0, 2.0], [2.0, 4.0]];
+        let error = angle_criterion(singular.view()).unwrap_err();
+        assert!(matches!(
+            error,
+            ConditionError::Singular {
+                criterion: Criterion::Angle
+            }
+        ));
+
+        let rectangular = array![[1.0, 2.0, 3.0], [4.0, 5.0, 6.0]];
+        let error = ConditionNumbers::new(rectangular.view()).unwrap_err();
+        assert!(matches!(
+            error,
+            ConditionError::NotSquare {
+                rows: 2,
+                columns: 3,
+                ..
+            }
+        ));
+
+        let empty = Array2::<f64>::zeros((0, 0));
+        let error = spectre_criterion(empty.view(), MatrixNorm::Spectral).unwrap_err();
+        assert!(matches!(
+            error,
+            ConditionError::Empty {
+                criterion: Criterion::Spectre
+            }
+        ));
+
+        let non_finite = array![[1.0, f64::NAN], [0.0, 1.0]];
+        let error = volume_criterion(non_finite.view()).unwrap_err();
+        assert!(matches!(
+            error,
+            ConditionError::NonFinite {
+                criterion: Criterion::Volume
+            }
+        ));
+    }
+
+    #[test]
+    fn report_in_both_languages() {
+        let matrix = array![[2.0, 0.0], [0.0, 1.0]];
+        let numbers = ConditionNumbers::with_norm(matrix.view(), MatrixNorm::One).unwrap();
+
+        assert_eq!(
+            numbers.to_string(),
+            "Спектральное число обусловленности (норма 1): 2.000\nОбъемное число обусловленности: 1.000\nУгловое число обусловленности: 1.000"
+        );
+        let cells = Row::from(numbers)
+            .iter()
+            .map(|cell| cell.get_content())
+            .collect::<Vec<_>>();
+        assert_eq!(cells, ["1", "2.000", "1.000", "1.000"]);
+        let style = ReportStyle {
+            language: Language::English,
+            precision: 1,
+            format: TableFormat::Markdown,
+        };
+        assert_eq!(
+            numbers.render(&style),
+            "| norm | spectre | volume | angle |\n| --- | --- | --- | --- |\n| 1 | 2.0 | 1.0 | 1.0 |\n"
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn condition_numbers_to_csv() {
+        use matrices::export::Export;
+
+        let matrix = array![[2.0, 0.0], [0.0, 1.0]];
+        let numbers = ConditionNumbers::with_norm(matrix.view(), MatrixNorm::One).unwrap();
+
+        let mut csv = Vec::new();
+        numbers.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "norm,spectre,volume,angle\nOne,2.0,1.0,1.0\n"
+        );
+    }
 }
@@ -0,0 +1,201 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use ndarray::ArrayView2;
+use ndarray_linalg::error::LinalgError;
+
+/// Quantity whose computation failed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Criterion {
+    Spectre,
+    Volume,
+    Angle,
+    /// Skeel and the other componentwise condition numbers.
+    Componentwise,
+    Eigenvalue,
+    /// Estimates of [`crate::ConditionEstimator`].
+    Estimate,
+}
+
+impl Display for Criterion {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Criterion::Spectre => "spectre",
+            Criterion::Volume => "volume",
+            Criterion::Angle => "angle",
+            Criterion::Componentwise => "componentwise",
+            Criterion::Eigenvalue => "eigenvalue",
+            Criterion::Estimate => "estimate",
+        };
+        write!(f, "{name}")
+    }
+}
+
+#[derive(Debug)]
+pub enum ConditionError {
+    NotSquare {
+        criterion: Criterion,
+        rows: usize,
+        columns: usize,
+    },
+    /// The matrix has no rows.
+    Empty { criterion: Criterion },
+    /// The matrix contains `NaN` or an infinity.
+    NonFinite { criterion: Criterion },
+    /// Zero determinant, zero pivot or zero smallest singular value.
+    Singular { criterion: Criterion },
+    /// The Jacobi SVD stopped after its maximum number of sweeps.
+    NotConverged { criterion: Criterion },
+    Lapack {
+        criterion: Criterion,
+        source: LinalgError,
+    },
+}
+
+impl ConditionError {
+    pub fn criterion(&self) -> Criterion {
+        match self {
+            ConditionError::NotSquare { criterion, .. }
+            | ConditionError::Empty { criterion }
+            | ConditionError::NonFinite { criterion }
+            | ConditionError::Singular { criterion }
+            | ConditionError::NotConverged { criterion }
+            | ConditionError::Lapack { criterion, .. } => *criterion,
+        }
+    }
+
+    pub(crate) fn lapack(criterion: Criterion) -> impl FnOnce(LinalgError) -> Self {
+        move |source| ConditionError::Lapack { criterion, source }
+    }
+
+    /// LU reports an exactly zero pivot as a computational failure (`info > 0`),
+    /// invalid arguments (`info < 0`) and other errors are passed through.
+    pub(crate) fn factorization(criterion: Criterion) -> impl FnOnce(LinalgError) -> Self {
+        move |source| match source {
+            LinalgError::Lapack(lax::error::Error::LapackComputationalFailure { .. }) => {
+                ConditionError::Singular { criterion }
+            }
+            source => ConditionError::Lapack { criterion, source },
+        }
+    }
+}
+
+impl Display for ConditionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConditionError::NotSquare {
+                criterion,
+                rows,
+                columns,
+            } => write!(
+                f,
+                "{criterion} criterion: matrix is not square ({rows}x{columns})"
+            ),
+            ConditionError::Empty { criterion } => {
+                write!(f, "{criterion} criterion: matrix is empty")
+            }
+            ConditionError::NonFinite { criterion } => {
+                write!(f, "{criterion} criterion: matrix has non-finite entries")
+            }
+            ConditionError::Singular { criterion } => {
+                write!(f, "{criterion} criterion: matrix is singular")
+            }
+            ConditionError::NotConverged { criterion } => {
+                write!(
+                    f,
+                    "{criterion} criterion: singular value decomposition did not converge"
+                )
+            }
+            ConditionError::Lapack { criterion, source } => {
+                write!(f, "{criterion} criterion: LAPACK failure: {source}")
+            }
+        }
+    }
+}
+
+impl Error for ConditionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConditionError::Lapack { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Rejects non-square and empty matrices and matrices with non-finite entries.
+pub(crate) fn check_matrix(
+    matrix: ArrayView2<f64>,
+    criterion: Criterion,
+) -> Result<(), ConditionError> {
+    let (rows, columns) = matrix.dim();
+    if rows != columns {
+        return Err(ConditionError::NotSquare {
+            criterion,
+            rows,
+            columns,
+        });
+    }
+    if rows == 0 {
+        return Err(ConditionError::Empty { criterion });
+    }
+    if matrix.iter().any(|a| !a.is_finite()) {
+        return Err(ConditionError::NonFinite { criterion });
+    }
+    Ok(())
+}
+
+/// Failure of [`crate::Experiment::run`].
+#[derive(Debug)]
+pub enum ExperimentError {
+    /// The solver returned `None`, `trial` is `None` for the unperturbed system.
+    SolverFailed {
+        trial: Option<usize>,
+    },
+    /// A `SingleEntry` outside the perturbed matrix or vector,
+    /// or a `RankOne` with vectors of other lengths.
+    PerturbationShape {
+        rows: usize,
+        columns: usize,
+        vector: usize,
+    },
+    Condition(ConditionError),
+}
+
+impl Display for ExperimentError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExperimentError::SolverFailed { trial: None } => {
+                write!(f, "solver failed on the original system")
+            }
+            ExperimentError::SolverFailed { trial: Some(trial) } => {
+                write!(f, "solver failed on the perturbed system of trial {trial}")
+            }
+            ExperimentError::PerturbationShape {
+                rows,
+                columns,
+                vector,
+            } => write!(
+                f,
+                "perturbation does not fit the {rows}x{columns} matrix and the vector of length {vector}"
+            ),
+            ExperimentError::Condition(error) => write!(f, "{error}"),
+        }
+    }
+}
+
+impl Error for ExperimentError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExperimentError::Condition(error) => Some(error),
+            ExperimentError::SolverFailed { .. } | ExperimentError::PerturbationShape { .. } => {
+                None
+            }
+        }
+    }
+}
+
+impl From<ConditionError> for ExperimentError {
+    fn from(error: ConditionError) -> Self {
+        ExperimentError::Condition(error)
+    }
+}
@@ -1,8 +1,35 @@
 use ndarray::{Array, ArrayView, Dimension};
 
+use matrices::{Language, ReportStyle, TableFormat};
+
+pub use backward::*;
+pub use componentwise::*;
 pub use condition::*;
+pub use error::*;
+pub use estimate::*;
+pub use norm::*;
+pub use perturbation::*;
+pub use refinement::*;
+pub use scaling::*;
+pub use svd::*;
 
+mod backward;
+mod componentwise;
 mod condition;
+mod error;
+mod estimate;
+mod norm;
+mod perturbation;
+mod refinement;
+mod scaling;
+mod svd;
+
+/// Style of the `*_table` methods, the English headers they always had.
+pub(crate) const TABLE_STYLE: ReportStyle = ReportStyle {
+    language: Language::English,
+    precision: 3,
+    format: TableFormat::Pretty,
+};
 
 pub fn add_number<D: Dimension>(matrix: ArrayView<f64, D>, number: f64) -> Array<f64, D> {
     matrix.map(|&a| a + number)
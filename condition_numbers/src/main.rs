@@ -1,14 +1,21 @@
 use ndarray::{ArrayView1, ArrayView2};
-use ndarray_linalg::{Norm, Solve};
-use prettytable::{Cell, Row, Table};
+use ndarray_linalg::Solve;
 
-use condition_numbers::{add_number, ConditionNumbers};
-use matrices::Examples;
+use condition_numbers::{
+    lu_solve, lu_solver_single, refine, svd_solve, BackwardErrors, ComponentwiseConditions,
+    ConditionEstimator, ConditionNumbers, EigenvalueConditions, Equilibration, Experiment,
+    ExperimentResult, MatrixNorm, Perturbation, Residual, Scaling, Svd, Target,
+};
+use matrices::{Examples, Language, Report, ReportStyle, ReportTable, TableFormat};
 
 const VARIATIONS: [f64; 8] = [10.0, 1.0, 0.1, 1e-2, 1e-4, 1e-6, 1e-8, 1e-10];
 const NEGATIVE_VARIATIONS: [f64; 8] = [-10.0, -1.0, -0.1, -1e-2, -1e-4, -1e-6, -1e-8, -1e-10];
+const TRIALS: usize = 10;
 
+/// `--english`, `--markdown`, `--latex` and `--precision <digits>` select the report style.
 fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let style = report_style()?;
+
     let bad_matrix = Examples::bad_matrix2();
     let vector = Examples::bad_vector2();
 
@@ -27,118 +34,222 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     let random_matrix = Examples::random_matrix(8);
     let random_vector = Examples::random_vector(8);
 
-    examine(bad_matrix.view(), vector.view(), "------ Bad matrix ------");
+    examine(
+        bad_matrix.view(),
+        vector.view(),
+        style.text("------ Плохая матрица ------", "------ Bad matrix ------"),
+        &style,
+    );
     examine(
         hilbert.view(),
         hilbert_vec.view(),
-        "------ Hilbert matrix ------",
+        style.text(
+            "------ Матрица Гильберта ------",
+            "------ Hilbert matrix ------",
+        ),
+        &style,
     );
     examine(
         tridiagonal.view(),
         vector_tridiagonal.view(),
-        "------ Tridiagonal matrix ------",
+        style.text(
+            "------ Трёхдиагональная матрица ------",
+            "------ Tridiagonal matrix ------",
+        ),
+        &style,
     );
     examine(
         diagonal.view(),
         vector_diagonal.view(),
-        "------ Diagonal matrix ------",
+        style.text(
+            "------ Диагональная матрица ------",
+            "------ Diagonal matrix ------",
+        ),
+        &style,
     );
     examine(
         random_matrix.view(),
         random_vector.view(),
-        "------ Random matrix ------",
+        style.text(
+            "------ Случайная матрица ------",
+            "------ Random matrix ------",
+        ),
+        &style,
     );
     Ok(())
 }
 
-fn examine(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, text: &str) {
+fn report_style() -> Result<ReportStyle, Box<dyn std::error::Error>> {
+    let mut style = ReportStyle::default();
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--english" => style.language = Language::English,
+            "--markdown" => style.format = TableFormat::Markdown,
+            "--latex" => style.format = TableFormat::Latex,
+            "--precision" => {
+                style.precision = args.next().ok_or("--precision needs a value")?.parse()?
+            }
+            _ => return Err(format!("unknown argument {arg}").into()),
+        }
+    }
+    Ok(style)
+}
+
+fn examine(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, text: &str, style: &ReportStyle) {
     println!("{}", text);
-    println!("Matrix");
+    println!("{}", style.text("Матрица", "Matrix"));
     println!("{:.3}", matrix);
 
-    println!("Vector: {vector:.3}");
+    println!("{}: {vector:.3}", style.text("Вектор", "Vector"));
     let x = matrix.solve(&vector).unwrap();
-    println!("Solution: {x:.2}");
-    let numbers = ConditionNumbers::new(matrix.view()).unwrap();
-
-    let table = numbers.condition_table();
-    table.printstd();
-
-    let mut table = Table::new();
-    let headers = push_back_to_row(
-        VARIATIONS.map(|num| format!("{num:e}")).to_vec(),
-        "".to_string(),
+    println!("{}: {x:.2}", style.text("Решение", "Solution"));
+    let numbers = ConditionNumbers::all_norms(matrix.view()).unwrap();
+
+    let table = ConditionNumbers::comparison_report(&numbers, style);
+    print!("{}", table.render(style.format));
+
+    let estimator = ConditionEstimator::new(matrix).unwrap();
+    println!(
+        "{}: {:.3} (1), {:.3} (2)",
+        style.text(
+            "Оценки чисел обусловленности",
+            "Estimated condition numbers"
+        ),
+        estimator.condition_one(),
+        estimator.condition_two(50, 1e-10)
     );
 
-    let matrix_variation = matrix_variations(matrix, vector, VARIATIONS.to_vec());
-    let matrix_neg_variation = matrix_variations(matrix, vector, NEGATIVE_VARIATIONS.to_vec());
+    let svd = Svd::new(matrix);
+    println!(
+        "{}: {:.3e}, {}: {}, {}: {:.2}",
+        style.text(
+            "SVD методом Якоби: сингулярные числа",
+            "Jacobi SVD: singular values"
+        ),
+        svd.singular_values(),
+        style.text("проходов", "sweeps"),
+        svd.sweeps(),
+        style.text("решение усечённым SVD", "truncated SVD solution"),
+        svd_solve(matrix, vector).unwrap()
+    );
 
-    let vector_variation = vector_variations(matrix, vector, VARIATIONS.to_vec());
-    let vector_neg_variation = vector_variations(matrix, vector, NEGATIVE_VARIATIONS.to_vec());
+    let componentwise = ComponentwiseConditions::new(matrix, vector).unwrap();
+    print!("{}", componentwise.render(style));
+    let eigenvalues = EigenvalueConditions::new(matrix).unwrap();
+    print!("{}", eigenvalues.render(style));
 
-    table.add_row(headers);
-    table.add_row(matrix_variation);
-    table.add_row(matrix_neg_variation);
-    table.add_row(vector_variation);
-    table.add_row(vector_neg_variation);
-    table.printstd()
+    let scaling = Scaling::new(matrix, Equilibration::Ruiz);
+    println!(
+        "{}",
+        style.text("Уравновешивание Руиса", "Ruiz equilibration")
+    );
+    let table = scaling.condition_report(matrix, style).unwrap();
+    print!("{}", table.render(style.format));
+
+    refinement(matrix, vector, style);
+
+    let mut header = vec!["".to_string()];
+    header.extend(VARIATIONS.map(|num| format!("{num:e}")));
+    let mut table = ReportTable::new(&header);
+
+    for (target, name) in [
+        (Target::Matrix, style.text("Матрица", "Matrix")),
+        (Target::Vector, style.text("Вектор", "Vector")),
+    ] {
+        let shift = |variations: &[f64], sign: &str| {
+            let results = variations_results(matrix, vector, target, variations, |var| {
+                Perturbation::Shift(var)
+            });
+            results_row(&results, format!("{name} ({sign})"), style, |result| {
+                result.mean_error()
+            })
+        };
+        table.add_row(shift(&VARIATIONS, "+"));
+        table.add_row(shift(&NEGATIVE_VARIATIONS, "-"));
+
+        let noise = variations_results(matrix, vector, target, &VARIATIONS, |var| {
+            Perturbation::RelativeNoise(var)
+        });
+        table.add_row(results_row(
+            &noise,
+            format!("{name} {}", style.text("шум", "noise")),
+            style,
+            |result| result.mean_error(),
+        ));
+        table.add_row(results_row(
+            &noise,
+            format!("{name} {}", style.text("оценка", "bound")),
+            style,
+            |result| result.bound(),
+        ));
+    }
+    print!("{}", table.render(style.format))
 }
 
-fn vector_variations(
-    matrix: ArrayView2<f64>,
-    vector: ArrayView1<f64>,
-    variations: Vec<f64>,
-) -> Row {
-    let vector_variations = variations
-        .iter()
-        .map(|&var| variate_vector_solve(matrix.view(), vector.view(), var))
-        .map(|variance| format!("{:.4}", variance))
-        .collect();
-
-    let row_name = if variations[0].is_sign_negative() {
-        "Vector (-)"
-    } else {
-        "Vector (+)"
-    };
+fn refinement(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, style: &ReportStyle) {
+    let solver = lu_solver_single(matrix).unwrap();
+    let x_single = solver(vector);
+    println!(
+        "{}: {x_single:.2}",
+        style.text("Решение в одинарной точности", "Single precision solution")
+    );
+    print!(
+        "{}",
+        BackwardErrors::new(matrix, vector, x_single.view()).render(style)
+    );
 
-    push_back_to_row(vector_variations, row_name.to_string())
+    let refined = refine(
+        matrix,
+        vector,
+        solver,
+        Residual::Compensated,
+        30,
+        f64::EPSILON,
+    );
+    println!(
+        "{}: {:.2}, {}: {}",
+        style.text("Уточнённое решение", "Refined solution"),
+        refined.x(),
+        style.text("шагов", "steps"),
+        refined.steps()
+    );
+    print!(
+        "{}",
+        BackwardErrors::new(matrix, vector, refined.x()).render(style)
+    );
 }
 
-fn matrix_variations(
+fn variations_results(
     matrix: ArrayView2<f64>,
     vector: ArrayView1<f64>,
-    variations: Vec<f64>,
-) -> Row {
-    let matrix_variations = variations
+    target: Target,
+    variations: &[f64],
+    perturbation: fn(f64) -> Perturbation,
+) -> Vec<ExperimentResult> {
+    variations
         .iter()
-        .map(|&var| variate_matrix_solve(matrix.view(), vector.view(), var))
-        .map(|variance| format!("{:.4}", variance))
-        .collect();
-
-    let row_name = if variations[0].is_sign_negative() {
-        "Matrix (-)"
-    } else {
-        "Matrix (+)"
-    };
-
-    push_back_to_row(matrix_variations, row_name.to_string())
+        .map(|&var| {
+            let experiment = Experiment {
+                matrix: matrix.view(),
+                vector: vector.view(),
+                target,
+                perturbation: perturbation(var),
+                trials: TRIALS,
+                norm: MatrixNorm::Spectral,
+            };
+            experiment.run(lu_solve).unwrap()
+        })
+        .collect()
 }
 
-fn push_back_to_row(mut vec: Vec<String>, text: String) -> Row {
-    vec.insert(0, text);
-    Row::new(vec.iter().map(|string| Cell::new(string)).collect())
-}
-
-fn variate_matrix_solve(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, var: f64) -> f64 {
-    let x = matrix.solve(&vector).unwrap();
-    let var_matrix = add_number(matrix, var);
-    let x_var = var_matrix.solve(&vector).unwrap();
-    (x - x_var).norm_l1()
-}
-
-fn variate_vector_solve(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, var: f64) -> f64 {
-    let x = matrix.solve(&vector).unwrap();
-    let var_vector = add_number(vector, var);
-    let x_var = matrix.solve(&var_vector).unwrap();
-    (x - x_var).norm_l1()
+fn results_row(
+    results: &[ExperimentResult],
+    name: String,
+    style: &ReportStyle,
+    value: fn(&ExperimentResult) -> f64,
+) -> Vec<String> {
+    let mut row = vec![name];
+    row.extend(results.iter().map(|result| style.number(value(result))));
+    row
 }
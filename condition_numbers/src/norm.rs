@@ -0,0 +1,101 @@
+use std::fmt::{Display, Formatter};
+
+use ndarray::{ArrayView1, ArrayView2, Axis};
+use ndarray_linalg::Norm;
+
+use crate::svd::Svd;
+
+/// Matrix norm used for the spectral condition number `||A|| ||A⁻¹||`.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum MatrixNorm {
+    /// Maximum absolute column sum.
+    One,
+    /// Maximum absolute row sum.
+    Infinity,
+    /// Largest singular value, `NaN` when the Jacobi SVD does not converge.
+    Spectral,
+    Frobenius,
+}
+
+impl MatrixNorm {
+    pub const ALL: [MatrixNorm; 4] = [
+        MatrixNorm::One,
+        MatrixNorm::Infinity,
+        MatrixNorm::Spectral,
+        MatrixNorm::Frobenius,
+    ];
+
+    pub fn of(&self, matrix: ArrayView2<f64>) -> f64 {
+        match self {
+            MatrixNorm::One => max_abs_sum(matrix, Axis(1)),
+            MatrixNorm::Infinity => max_abs_sum(matrix, Axis(0)),
+            MatrixNorm::Spectral => singular_values(matrix).map_or(f64::NAN, |values| values[0]),
+            MatrixNorm::Frobenius => matrix.norm(),
+        }
+    }
+
+    /// Vector norm consistent with this matrix norm.
+    pub fn of_vector(&self, vector: ArrayView1<f64>) -> f64 {
+        match self {
+            MatrixNorm::One => vector.norm_l1(),
+            MatrixNorm::Infinity => vector.norm_max(),
+            MatrixNorm::Spectral | MatrixNorm::Frobenius => vector.norm_l2(),
+        }
+    }
+}
+
+impl Display for MatrixNorm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            MatrixNorm::One => "1",
+            MatrixNorm::Infinity => "inf",
+            MatrixNorm::Spectral => "2",
+            MatrixNorm::Frobenius => "F",
+        };
+        write!(f, "{name}")
+    }
+}
+
+/// Singular values in non-increasing order, `None` when the Jacobi SVD does not converge.
+pub(crate) fn singular_values(matrix: ArrayView2<f64>) -> Option<Vec<f64>> {
+    let svd = Svd::new(matrix);
+    svd.converged().then(|| svd.singular_values().to_vec())
+}
+
+/// `max_i |v_i|`.
+pub(crate) fn max_abs(vector: ArrayView1<f64>) -> f64 {
+    vector.iter().fold(0.0, |acc, a| acc.max(a.abs()))
+}
+
+fn max_abs_sum(matrix: ArrayView2<f64>, axis: Axis) -> f64 {
+    matrix
+        .axis_iter(axis)
+        .map(|lane| lane.iter().map(|a| a.abs()).sum::<f64>())
+        .fold(0.0, f64::max)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_approx_eq::assert_approx_eq;
+    use ndarray::array;
+
+    use crate::norm::MatrixNorm;
+
+    #[test]
+    fn norms_of_small_matrix() {
+        let matrix = array![[1.0, -2.0], [3.0, 4.0]];
+
+        assert_approx_eq!(MatrixNorm::One.of(matrix.view()), 6.0);
+        assert_approx_eq!(MatrixNorm::Infinity.of(matrix.view()), 7.0);
+        assert_approx_eq!(MatrixNorm::Frobenius.of(matrix.view()), 30.0_f64.sqrt());
+        // AᵀA = [[10, 10], [10, 20]], λ_max = 15 + 5√5
+        assert_approx_eq!(
+            MatrixNorm::Spectral.of(matrix.view()),
+            (15.0 + 5.0 * 5.0_f64.sqrt()).sqrt()
+        );
+        // NaN spoils every rotation, the SVD never converges
+        let nan = array![[1.0, f64::NAN], [3.0, 4.0]];
+        assert!(MatrixNorm::Spectral.of(nan.view()).is_nan());
+    }
+}
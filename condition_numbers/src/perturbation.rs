@@ -0,0 +1,390 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use ndarray_linalg::Solve;
+use rand::random;
+
+use crate::add_number;
+use crate::condition::spectre_criterion;
+use crate::error::ExperimentError;
+use crate::norm::MatrixNorm;
+
+/// How the matrix or the right-hand side is perturbed in a single trial.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Perturbation {
+    /// Adds the same number to every entry.
+    Shift(f64),
+    /// Multiplies every entry by `1 + δξ` with `ξ` uniform in `[-1, 1]`, new `ξ` in every trial.
+    RelativeNoise(f64),
+    /// Adds `value` to a single entry; `column` is ignored for vectors.
+    SingleEntry {
+        row: usize,
+        column: usize,
+        value: f64,
+    },
+    /// Adds `scale * u vᵀ` to a matrix or `scale * u` to a vector.
+    RankOne {
+        u: Array1<f64>,
+        v: Array1<f64>,
+        scale: f64,
+    },
+}
+
+impl Perturbation {
+    pub fn matrix(&self, matrix: ArrayView2<f64>) -> Array2<f64> {
+        match self {
+            Perturbation::Shift(number) => add_number(matrix, *number),
+            Perturbation::RelativeNoise(delta) => matrix.mapv(|a| a * (1.0 + delta * noise())),
+            Perturbation::SingleEntry { row, column, value } => {
+                let mut matrix = matrix.to_owned();
+                matrix[(*row, *column)] += value;
+                matrix
+            }
+            Perturbation::RankOne { u, v, scale } => {
+                let outer = u
+                    .view()
+                    .insert_axis(Axis(1))
+                    .dot(&v.view().insert_axis(Axis(0)));
+                &matrix + &(*scale * outer)
+            }
+        }
+    }
+
+    /// Whether a `SingleEntry` lies inside and a `RankOne` has the lengths of
+    /// a `rows × columns` matrix, or of a vector of length `rows` for `columns == None`.
+    fn fits(&self, rows: usize, columns: Option<usize>) -> bool {
+        match self {
+            Perturbation::Shift(_) | Perturbation::RelativeNoise(_) => true,
+            Perturbation::SingleEntry { row, column, .. } => {
+                *row < rows && columns.is_none_or(|columns| *column < columns)
+            }
+            Perturbation::RankOne { u, v, .. } => {
+                u.len() == rows && columns.is_none_or(|columns| v.len() == columns)
+            }
+        }
+    }
+
+    pub fn vector(&self, vector: ArrayView1<f64>) -> Array1<f64> {
+        match self {
+            Perturbation::Shift(number) => add_number(vector, *number),
+            Perturbation::RelativeNoise(delta) => vector.mapv(|a| a * (1.0 + delta * noise())),
+            Perturbation::SingleEntry { row, value, .. } => {
+                let mut vector = vector.to_owned();
+                vector[*row] += value;
+                vector
+            }
+            Perturbation::RankOne { u, scale, .. } => &vector + &(*scale * u),
+        }
+    }
+}
+
+fn noise() -> f64 {
+    2.0 * random::<f64>() - 1.0
+}
+
+/// Which side of `Ax = b` is perturbed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Target {
+    Matrix,
+    Vector,
+}
+
+pub struct Experiment<'a> {
+    pub matrix: ArrayView2<'a, f64>,
+    pub vector: ArrayView1<'a, f64>,
+    pub target: Target,
+    pub perturbation: Perturbation,
+    pub trials: usize,
+    pub norm: MatrixNorm,
+}
+
+impl Experiment<'_> {
+    /// Runs `trials` perturbed solves, `solver` returns `None` when it fails.
+    pub fn run<S>(&self, solver: S) -> Result<ExperimentResult, ExperimentError>
+    where
+        S: Fn(ArrayView2<f64>, ArrayView1<f64>) -> Option<Array1<f64>>,
+    {
+        let (rows, columns) = self.matrix.dim();
+        let fits = match self.target {
+            Target::Matrix => self.perturbation.fits(rows, Some(columns)),
+            Target::Vector => self.perturbation.fits(self.vector.len(), None),
+        };
+        if !fits {
+            return Err(ExperimentError::PerturbationShape {
+                rows,
+                columns,
+                vector: self.vector.len(),
+            });
+        }
+
+        // a spectral norm is a whole SVD, both are computed once
+        let condition = spectre_criterion(self.matrix, self.norm)?;
+        let (matrix_norm, vector_norm) = match self.target {
+            Target::Matrix => (self.norm.of(self.matrix), 0.0),
+            Target::Vector => (0.0, self.norm.of_vector(self.vector)),
+        };
+
+        let x = solver(self.matrix, self.vector)
+            .ok_or(ExperimentError::SolverFailed { trial: None })?;
+        let x_norm = self.norm.of_vector(x.view());
+
+        let mut perturbations = Vec::with_capacity(self.trials);
+        let mut errors = Vec::with_capacity(self.trials);
+        for trial in 0..self.trials {
+            let (x_perturbed, perturbation) = match self.target {
+                Target::Matrix => {
+                    let matrix = self.perturbation.matrix(self.matrix);
+                    let relative = self.norm.of((&matrix - &self.matrix).view()) / matrix_norm;
+                    (solver(matrix.view(), self.vector), relative)
+                }
+                Target::Vector => {
+                    let vector = self.perturbation.vector(self.vector);
+                    let relative =
+                        self.norm.of_vector((&vector - &self.vector).view()) / vector_norm;
+                    (solver(self.matrix, vector.view()), relative)
+                }
+            };
+            let x_perturbed =
+                x_perturbed.ok_or(ExperimentError::SolverFailed { trial: Some(trial) })?;
+
+            perturbations.push(perturbation);
+            errors.push(self.norm.of_vector((&x_perturbed - &x).view()) / x_norm);
+        }
+
+        Ok(ExperimentResult {
+            target: self.target,
+            condition,
+            perturbations,
+            errors,
+        })
+    }
+}
+
+/// Default solver for [`Experiment::run`], LAPACK LU.
+pub fn lu_solve(matrix: ArrayView2<f64>, vector: ArrayView1<f64>) -> Option<Array1<f64>> {
+    matrix.solve(&vector).ok()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ExperimentResult {
+    target: Target,
+    condition: f64,
+    perturbations: Vec<f64>,
+    errors: Vec<f64>,
+}
+
+impl ExperimentResult {
+    pub fn condition(&self) -> f64 {
+        self.condition
+    }
+
+    /// Relative perturbations `||ΔA|| / ||A||` or `||Δb|| / ||b||`, one per trial.
+    pub fn perturbations(&self) -> &[f64] {
+        &self.perturbations
+    }
+
+    /// Relative errors `||x̃ - x|| / ||x||`, one per trial.
+    pub fn errors(&self) -> &[f64] {
+        &self.errors
+    }
+
+    pub fn mean_error(&self) -> f64 {
+        self.errors.iter().sum::<f64>() / self.errors.len() as f64
+    }
+
+    pub fn error_deviation(&self) -> f64 {
+        let mean = self.mean_error();
+        let variance =
+            self.errors.iter().map(|e| (e - mean).powi(2)).sum::<f64>() / self.errors.len() as f64;
+        variance.sqrt()
+    }
+
+    pub fn min_error(&self) -> f64 {
+        self.errors.iter().copied().fold(f64::INFINITY, f64::min)
+    }
+
+    pub fn max_error(&self) -> f64 {
+        self.errors.iter().copied().fold(0.0, f64::max)
+    }
+
+    /// [`ExperimentResult::bound_for`] the largest relative perturbation.
+    pub fn bound(&self) -> f64 {
+        self.bound_for(self.perturbations.iter().copied().fold(0.0, f64::max))
+    }
+
+    /// `κδ` for a right-hand side perturbed by `δ`, `κδ / (1 - κδ)` for a matrix,
+    /// infinite once `κδ >= 1` and the perturbed matrix may be singular.
+    pub fn bound_for(&self, perturbation: f64) -> f64 {
+        let first_order = self.condition * perturbation;
+        match self.target {
+            Target::Vector => first_order,
+            Target::Matrix if first_order < 1.0 => first_order / (1.0 - first_order),
+            Target::Matrix => f64::INFINITY,
+        }
+    }
+
+    /// Whether every trial stays within [`ExperimentResult::bound_for`] its perturbation.
+    pub fn within_bound(&self) -> bool {
+        self.errors
+            .iter()
+            .zip(self.perturbations.iter())
+            .all(|(error, perturbation)| *error <= self.bound_for(*perturbation))
+    }
+}
+
+/// One trial of an [`ExperimentResult`] in its CSV table.
+#[cfg(feature = "serde")]
+#[derive(serde::Serialize)]
+pub struct TrialRecord {
+    pub trial: usize,
+    pub perturbation: f64,
+    pub error: f64,
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for ExperimentResult {
+    type Record = TrialRecord;
+
+    fn records(&self) -> Vec<TrialRecord> {
+        self.perturbations
+            .iter()
+            .zip(self.errors.iter())
+            .enumerate()
+            .map(|(trial, (&perturbation, &error))| TrialRecord {
+                trial,
+                perturbation,
+                error,
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+
+    use matrices::Examples;
+
+    use crate::error::ExperimentError;
+    use crate::norm::MatrixNorm;
+    use crate::perturbation::{lu_solve, Experiment, Perturbation, Target};
+
+    #[test]
+    fn perturbations_change_expected_entries() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+
+        let single = Perturbation::SingleEntry {
+            row: 1,
+            column: 0,
+            value: 0.5,
+        };
+        assert_eq!(single.matrix(matrix.view()), array![[1.0, 2.0], [3.5, 4.0]]);
+
+        let rank_one = Perturbation::RankOne {
+            u: array![1.0, 2.0],
+            v: array![1.0, -1.0],
+            scale: 0.5,
+        };
+        assert_eq!(
+            rank_one.matrix(matrix.view()),
+            array![[1.5, 1.5], [4.0, 3.0]]
+        );
+        assert_eq!(rank_one.vector(array![0.0, 0.0].view()), array![0.5, 1.0]);
+    }
+
+    #[test]
+    fn vector_noise_stays_within_bound() {
+        let matrix = Examples::hilbert(6);
+        let vector = Examples::random_vector(6);
+
+        let experiment = Experiment {
+            matrix: matrix.view(),
+            vector: vector.view(),
+            target: Target::Vector,
+            perturbation: Perturbation::RelativeNoise(1e-6),
+            trials: 20,
+            norm: MatrixNorm::Spectral,
+        };
+        let result = experiment.run(lu_solve).unwrap();
+
+        assert_eq!(result.errors().len(), 20);
+        assert!(result.within_bound());
+        assert!(result.max_error() <= result.bound());
+    }
+
+    #[test]
+    fn matrix_bound_includes_second_order() {
+        let matrix = array![[1.0, 0.0], [0.0, 1.0]];
+        let vector = array![1.0, 0.0];
+
+        // A + ΔA = diag(0.5, 1) doubles x: error 1 above κδ = 0.5, equal to κδ / (1 - κδ)
+        let experiment = Experiment {
+            matrix: matrix.view(),
+            vector: vector.view(),
+            target: Target::Matrix,
+            perturbation: Perturbation::RankOne {
+                u: array![1.0, 0.0],
+                v: array![1.0, 0.0],
+                scale: -0.5,
+            },
+            trials: 1,
+            norm: MatrixNorm::Infinity,
+        };
+        let result = experiment.run(lu_solve).unwrap();
+
+        assert_eq!(result.errors(), [1.0]);
+        assert_eq!(result.bound(), 1.0);
+        assert!(result.within_bound());
+        assert_eq!(result.bound_for(1.0), f64::INFINITY);
+    }
+
+    #[test]
+    fn mismatched_perturbations_are_rejected() {
+        let matrix = array![[1.0, 2.0], [3.0, 4.0]];
+        let vector = array![1.0, 1.0];
+
+        for (target, perturbation) in [
+            (
+                Target::Matrix,
+                Perturbation::SingleEntry {
+                    row: 0,
+                    column: 2,
+                    value: 1.0,
+                },
+            ),
+            (
+                Target::Vector,
+                Perturbation::SingleEntry {
+                    row: 2,
+                    column: 0,
+                    value: 1.0,
+                },
+            ),
+            (
+                Target::Matrix,
+                Perturbation::RankOne {
+                    u: array![1.0, 2.0],
+                    v: array![1.0],
+                    scale: 1.0,
+                },
+            ),
+        ] {
+            let experiment = Experiment {
+                matrix: matrix.view(),
+                vector: vector.view(),
+                target,
+                perturbation,
+                trials: 1,
+                norm: MatrixNorm::One,
+            };
+            assert!(matches!(
+                experiment.run(lu_solve),
+                Err(ExperimentError::PerturbationShape {
+                    rows: 2,
+                    columns: 2,
+                    vector: 2
+                })
+            ));
+        }
+    }
+}
@@ -0,0 +1,249 @@
+use ndarray::{Array1, ArrayView1, ArrayView2, Zip};
+use ndarray_linalg::error::LinalgError;
+use ndarray_linalg::{Factorize, Solve};
+
+use crate::norm::max_abs;
+
+/// How the residual `r = b - Ax` is accumulated during refinement.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Residual {
+    /// Plain f64 dot products.
+    Double,
+    /// Compensated dot products (Ogita–Rump–Oishi `Dot2`),
+    /// as accurate as if computed in twice the working precision.
+    Compensated,
+}
+
+#[derive(Debug)]
+pub struct RefinementResult {
+    x: Array1<f64>,
+    corrections: Vec<f64>,
+    steps: u32,
+    converged: bool,
+}
+
+impl RefinementResult {
+    pub fn x(&self) -> ArrayView1<'_, f64> {
+        self.x.view()
+    }
+
+    /// Relative sizes `||d||∞ / ||x||∞` of the corrections, one per step.
+    pub fn corrections(&self) -> &[f64] {
+        &self.corrections
+    }
+
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
+
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+/// Iterative refinement of the solution of `Ax = b`.
+///
+/// `solver` solves a system with the same matrix for the given right-hand side,
+/// so any solver of the workspace can be plugged in, e.g.
+/// `|r| solve_qr(matrix, r).unwrap()` or a factorisation from [`lu_solver_single`].
+///
+/// Stops when the relative correction drops below `epsilon`,
+/// when corrections stop decreasing or after `max_steps` steps.
+pub fn refine<S>(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    solver: S,
+    residual: Residual,
+    max_steps: u32,
+    epsilon: f64,
+) -> RefinementResult
+where
+    S: Fn(ArrayView1<f64>) -> Array1<f64>,
+{
+    let mut x = solver(vector);
+    let mut corrections = Vec::new();
+    let mut steps = 0;
+    let mut converged = false;
+
+    while steps < max_steps {
+        let r = residual_vector(matrix, vector, x.view(), residual);
+        let d = solver(r.view());
+        x += &d;
+        steps += 1;
+
+        // x = 0 solves b = 0 exactly, and 0 / 0 would never pass the stop tests
+        let norm = max_abs(x.view());
+        let correction = if norm == 0.0 {
+            0.0
+        } else {
+            max_abs(d.view()) / norm
+        };
+        let stagnated = corrections
+            .last()
+            .is_some_and(|&last| correction > last / 2.0);
+        corrections.push(correction);
+
+        if correction <= epsilon {
+            converged = true;
+            break;
+        }
+        if stagnated {
+            break;
+        }
+    }
+
+    RefinementResult {
+        x,
+        corrections,
+        steps,
+        converged,
+    }
+}
+
+/// Solver reusing one f64 LU factorisation of `matrix`.
+pub fn lu_solver(
+    matrix: ArrayView2<f64>,
+) -> Result<impl Fn(ArrayView1<f64>) -> Array1<f64>, LinalgError> {
+    let lu = matrix.factorize()?;
+    Ok(move |vector: ArrayView1<f64>| lu.solve(&vector).unwrap())
+}
+
+/// Solver reusing one f32 LU factorisation of `matrix`.
+///
+/// The right-hand side is scaled before rounding to f32, so small residuals do not underflow.
+pub fn lu_solver_single(
+    matrix: ArrayView2<f64>,
+) -> Result<impl Fn(ArrayView1<f64>) -> Array1<f64>, LinalgError> {
+    let lu = matrix.mapv(|a| a as f32).factorize()?;
+    Ok(move |vector: ArrayView1<f64>| {
+        let scale = max_abs(vector);
+        if scale == 0.0 {
+            return Array1::zeros(vector.len());
+        }
+
+        let scaled = vector.mapv(|a| (a / scale) as f32);
+        lu.solve(&scaled).unwrap().mapv(|a| f64::from(a) * scale)
+    })
+}
+
+/// Residual `b - Ax` accumulated as described by `residual`.
+pub fn residual_vector(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    x: ArrayView1<f64>,
+    residual: Residual,
+) -> Array1<f64> {
+    match residual {
+        Residual::Double => &vector - &matrix.dot(&x),
+        Residual::Compensated => Zip::from(matrix.rows())
+            .and(&vector)
+            .map_collect(|row, &b| compensated_residual(row, x, b)),
+    }
+}
+
+fn compensated_residual(row: ArrayView1<f64>, x: ArrayView1<f64>, b: f64) -> f64 {
+    let (mut sum, mut error) = (b, 0.0);
+    for (&a, &x) in row.iter().zip(x.iter()) {
+        let (product, product_error) = two_product(a, -x);
+        let (next, sum_error) = two_sum(sum, product);
+        sum = next;
+        error += product_error + sum_error;
+    }
+    sum + error
+}
+
+fn two_sum(a: f64, b: f64) -> (f64, f64) {
+    let sum = a + b;
+    let z = sum - a;
+    (sum, (a - (sum - z)) + (b - z))
+}
+
+fn two_product(a: f64, b: f64) -> (f64, f64) {
+    let product = a * b;
+    (product, a.mul_add(b, -product))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1};
+
+    use matrices::Examples;
+
+    use crate::refinement::{lu_solver, lu_solver_single, refine, residual_vector, Residual};
+
+    #[test]
+    fn compensated_residual_is_exact_on_cancellation() {
+        let matrix = array![[1e16, 1.0, -1e16]];
+        let vector = array![0.0];
+        let x = array![1.0, 1.0, 1.0];
+
+        let residual = residual_vector(
+            matrix.view(),
+            vector.view(),
+            x.view(),
+            Residual::Compensated,
+        );
+        assert_eq!(residual[0], -1.0);
+    }
+
+    #[test]
+    fn single_precision_refinement_reaches_double_accuracy() {
+        let matrix = Examples::hilbert(4);
+        let x_exact = Array1::ones(4);
+        let vector = matrix.dot(&x_exact);
+
+        let solver = lu_solver_single(matrix.view()).unwrap();
+        let unrefined = solver(vector.view());
+        let result = refine(
+            matrix.view(),
+            vector.view(),
+            solver,
+            Residual::Compensated,
+            50,
+            1e-14,
+        );
+
+        assert!(result.converged());
+        assert!((&unrefined - &x_exact).iter().any(|a| a.abs() > 1e-6));
+        assert!(result.x().abs_diff_eq(&x_exact, 1e-11));
+    }
+
+    #[test]
+    fn double_precision_refinement_converges() {
+        let matrix = Examples::hilbert(6);
+        let x_exact = Array1::ones(6);
+        let vector = matrix.dot(&x_exact);
+
+        let solver = lu_solver(matrix.view()).unwrap();
+        let result = refine(
+            matrix.view(),
+            vector.view(),
+            solver,
+            Residual::Double,
+            10,
+            1e-12,
+        );
+
+        assert!(result.x().abs_diff_eq(&x_exact, 1e-8));
+    }
+
+    #[test]
+    fn zero_solution_converges_at_once() {
+        let matrix = Examples::hilbert(4);
+        let vector = Array1::zeros(4);
+
+        let solver = lu_solver(matrix.view()).unwrap();
+        let result = refine(
+            matrix.view(),
+            vector.view(),
+            solver,
+            Residual::Double,
+            10,
+            1e-12,
+        );
+
+        assert!(result.converged());
+        assert_eq!(result.steps(), 1);
+        assert_eq!(result.corrections(), [0.0]);
+    }
+}
@@ -0,0 +1,231 @@
+use matrices::{ReportStyle, ReportTable};
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use prettytable::Table;
+
+use crate::condition::ConditionNumbers;
+use crate::error::ConditionError;
+use crate::norm::max_abs;
+use crate::TABLE_STYLE;
+
+const RUIZ_MAX_ITERATIONS: usize = 50;
+const RUIZ_TOLERANCE: f64 = 1e-8;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Equilibration {
+    /// Every row scaled to unit ∞-norm.
+    Row,
+    /// Every column scaled to unit ∞-norm.
+    Column,
+    /// Iterative two-sided ∞-norm equilibration of Ruiz.
+    Ruiz,
+    /// Rows and then columns scaled to unit 2-norm.
+    /// Van der Sluis showed that either one-sided scaling alone is optimal up to a factor of √n
+    /// among scalings of that side, the two applied one after the other carry no such bound.
+    VanDerSluis,
+}
+
+/// Diagonal scaling `D_r A D_c` of a matrix.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scaling {
+    row: Array1<f64>,
+    column: Array1<f64>,
+}
+
+impl Scaling {
+    pub fn new(matrix: ArrayView2<f64>, equilibration: Equilibration) -> Self {
+        let (rows, columns) = matrix.dim();
+        match equilibration {
+            Equilibration::Row => Self {
+                row: reciprocal(lane_norms(matrix, Axis(0), max_abs)),
+                column: Array1::ones(columns),
+            },
+            Equilibration::Column => Self {
+                row: Array1::ones(rows),
+                column: reciprocal(lane_norms(matrix, Axis(1), max_abs)),
+            },
+            Equilibration::Ruiz => Self::ruiz(matrix),
+            Equilibration::VanDerSluis => {
+                let row = reciprocal(lane_norms(matrix, Axis(0), euclidean));
+                let row_scaled = &matrix * &row.view().insert_axis(Axis(1));
+                let column = reciprocal(lane_norms(row_scaled.view(), Axis(1), euclidean));
+                Self { row, column }
+            }
+        }
+    }
+
+    fn ruiz(matrix: ArrayView2<f64>) -> Self {
+        let (rows, columns) = matrix.dim();
+        let mut scaling = Self {
+            row: Array1::ones(rows),
+            column: Array1::ones(columns),
+        };
+
+        for _ in 0..RUIZ_MAX_ITERATIONS {
+            let scaled = scaling.scale_matrix(matrix);
+            let row_norms = lane_norms(scaled.view(), Axis(0), max_abs);
+            let column_norms = lane_norms(scaled.view(), Axis(1), max_abs);
+
+            let deviation = row_norms
+                .iter()
+                .chain(column_norms.iter())
+                .filter(|&&norm| norm != 0.0)
+                .map(|norm| (1.0 - norm).abs())
+                .fold(0.0, f64::max);
+            if deviation <= RUIZ_TOLERANCE {
+                break;
+            }
+
+            scaling.row *= &reciprocal(row_norms.mapv(f64::sqrt));
+            scaling.column *= &reciprocal(column_norms.mapv(f64::sqrt));
+        }
+
+        scaling
+    }
+
+    pub fn row(&self) -> ArrayView1<'_, f64> {
+        self.row.view()
+    }
+
+    pub fn column(&self) -> ArrayView1<'_, f64> {
+        self.column.view()
+    }
+
+    pub fn scale_matrix(&self, matrix: ArrayView2<f64>) -> Array2<f64> {
+        let row_scaled = &matrix * &self.row.view().insert_axis(Axis(1));
+        row_scaled * self.column.view().insert_axis(Axis(0))
+    }
+
+    pub fn scale_vector(&self, vector: ArrayView1<f64>) -> Array1<f64> {
+        &vector * &self.row
+    }
+
+    /// `x = D_c y` for the solution `y` of the scaled system.
+    pub fn unscale_solution(&self, y: ArrayView1<f64>) -> Array1<f64> {
+        &y * &self.column
+    }
+
+    /// Solves `(D_r A D_c) y = D_r b` and returns `x = D_c y`.
+    pub fn solve<S>(
+        &self,
+        matrix: ArrayView2<f64>,
+        vector: ArrayView1<f64>,
+        solver: S,
+    ) -> Option<Array1<f64>>
+    where
+        S: Fn(ArrayView2<f64>, ArrayView1<f64>) -> Option<Array1<f64>>,
+    {
+        let matrix = self.scale_matrix(matrix);
+        let vector = self.scale_vector(vector);
+        let y = solver(matrix.view(), vector.view())?;
+        Some(self.unscale_solution(y.view()))
+    }
+
+    /// Condition numbers of the original and the scaled matrix.
+    pub fn conditions(
+        &self,
+        matrix: ArrayView2<f64>,
+    ) -> Result<(ConditionNumbers, ConditionNumbers), ConditionError> {
+        let original = ConditionNumbers::new(matrix)?;
+        let scaled = ConditionNumbers::new(self.scale_matrix(matrix).view())?;
+        Ok((original, scaled))
+    }
+
+    pub fn condition_table(&self, matrix: ArrayView2<f64>) -> Result<Table, ConditionError> {
+        Ok(self.condition_report(matrix, &TABLE_STYLE)?.pretty())
+    }
+
+    pub fn condition_report(
+        &self,
+        matrix: ArrayView2<f64>,
+        style: &ReportStyle,
+    ) -> Result<ReportTable, ConditionError> {
+        let (original, scaled) = self.conditions(matrix)?;
+        let numbers = ConditionNumbers::comparison_report(&[original, scaled], style);
+
+        let mut header = vec![String::new()];
+        header.extend_from_slice(numbers.header());
+        let mut table = ReportTable::new(&header);
+        let names = [
+            style.text("исходная", "original"),
+            style.text("масштабированная", "scaled"),
+        ];
+        for (name, row) in names.iter().zip(numbers.rows()) {
+            let mut row = row.clone();
+            row.insert(0, name.to_string());
+            table.add_row(row);
+        }
+        Ok(table)
+    }
+}
+
+fn lane_norms(
+    matrix: ArrayView2<f64>,
+    axis: Axis,
+    norm: fn(ArrayView1<f64>) -> f64,
+) -> Array1<f64> {
+    matrix.axis_iter(axis).map(norm).collect()
+}
+
+fn euclidean(lane: ArrayView1<f64>) -> f64 {
+    lane.iter().map(|a| a * a).sum::<f64>().sqrt()
+}
+
+/// Zero rows and columns are left unscaled.
+fn reciprocal(norms: Array1<f64>) -> Array1<f64> {
+    norms.mapv(|norm| if norm == 0.0 { 1.0 } else { 1.0 / norm })
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Axis};
+
+    use crate::norm::max_abs;
+    use crate::perturbation::lu_solve;
+    use crate::scaling::{Equilibration, Scaling};
+
+    #[test]
+    fn ruiz_equilibrates_rows_and_columns() {
+        let matrix = array![[1e6, 2.0, 0.0], [3.0, 4e-3, 5.0], [0.0, 6.0, 7e4]];
+
+        let scaling = Scaling::new(matrix.view(), Equilibration::Ruiz);
+        let scaled = scaling.scale_matrix(matrix.view());
+        for lane in scaled.axis_iter(Axis(0)).chain(scaled.axis_iter(Axis(1))) {
+            assert!((max_abs(lane) - 1.0).abs() < 1e-6);
+        }
+    }
+
+    #[test]
+    fn row_scaling_improves_badly_scaled_matrix() {
+        let matrix = array![[1e8, 2e8], [3.0, 4.0]];
+        let vector = array![3e8, 7.0];
+
+        let scaling = Scaling::new(matrix.view(), Equilibration::Row);
+        let (original, scaled) = scaling.conditions(matrix.view()).unwrap();
+        assert!(scaled.spectre() * 1e4 < original.spectre());
+
+        let x = scaling
+            .solve(matrix.view(), vector.view(), lu_solve)
+            .unwrap();
+        assert!(x.abs_diff_eq(&array![1.0, 1.0], 1e-10));
+    }
+
+    #[test]
+    fn every_equilibration_keeps_solution() {
+        let matrix = array![[4.0, 1e-3, 2.0], [1e3, 5e2, 1.0], [2.0, 1.0, 3e-2]];
+        let vector = array![1.0, 2.0, 3.0];
+        let x = lu_solve(matrix.view(), vector.view()).unwrap();
+
+        for equilibration in [
+            Equilibration::Row,
+            Equilibration::Column,
+            Equilibration::Ruiz,
+            Equilibration::VanDerSluis,
+        ] {
+            let scaling = Scaling::new(matrix.view(), equilibration);
+            let x_scaled = scaling
+                .solve(matrix.view(), vector.view(), lu_solve)
+                .unwrap();
+            assert!(x.abs_diff_eq(&x_scaled, 1e-9));
+        }
+    }
+}
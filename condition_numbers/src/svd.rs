@@ -0,0 +1,238 @@
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, Axis, ShapeBuilder, Zip};
+
+const MAX_SWEEPS: u32 = 60;
+
+/// Thin singular value decomposition `A = U Σ Vᵀ` by one-sided Jacobi (Hestenes) rotations.
+///
+/// For an `m × n` matrix with `k = min(m, n)`, `U` is `m × k`, `Vᵀ` is `k × n`
+/// and the singular values are in non-increasing order.
+/// Columns of `U` for zero singular values are zero.
+/// After `MAX_SWEEPS` sweeps the decomposition is returned unconverged, see [`Svd::converged`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct Svd {
+    u: Array2<f64>,
+    singular_values: Array1<f64>,
+    vt: Array2<f64>,
+    sweeps: u32,
+    converged: bool,
+}
+
+impl Svd {
+    /// [`Svd::with_tolerance`] `ε max(m, n)`, the rounding level of the column inner products.
+    pub fn new(matrix: ArrayView2<f64>) -> Self {
+        let (m, n) = matrix.dim();
+        Self::with_tolerance(matrix, f64::EPSILON * m.max(n) as f64)
+    }
+
+    /// Columns `i` and `j` are rotated while `|aᵢᵀaⱼ| > epsilon ||aᵢ|| ||aⱼ||`.
+    pub fn with_tolerance(matrix: ArrayView2<f64>, epsilon: f64) -> Self {
+        let (m, n) = matrix.dim();
+        if m < n {
+            // Aᵀ = V Σ Uᵀ
+            let transposed = Self::with_tolerance(matrix.t(), epsilon);
+            return Self {
+                u: transposed.vt.reversed_axes(),
+                singular_values: transposed.singular_values,
+                vt: transposed.u.reversed_axes(),
+                sweeps: transposed.sweeps,
+                converged: transposed.converged,
+            };
+        }
+
+        // column-major, so that columns are contiguous
+        let mut u = Array2::zeros((m, n).f());
+        u.assign(&matrix);
+        let mut v = Array2::zeros((n, n).f());
+        v.diag_mut().fill(1.0);
+
+        let mut sweeps = 0;
+        let mut converged = false;
+        while sweeps < MAX_SWEEPS {
+            sweeps += 1;
+            let mut rotated = false;
+            for i in 0..n {
+                for j in i + 1..n {
+                    let (alpha, beta, gamma) = {
+                        let (a, b) = (u.column(i), u.column(j));
+                        (a.dot(&a), b.dot(&b), a.dot(&b))
+                    };
+                    if gamma == 0.0 || gamma.abs() <= epsilon * (alpha * beta).sqrt() {
+                        continue;
+                    }
+                    rotated = true;
+
+                    let zeta = (beta - alpha) / (2.0 * gamma);
+                    let t = zeta.signum() / (zeta.abs() + (1.0 + zeta * zeta).sqrt());
+                    let cos = 1.0 / (1.0 + t * t).sqrt();
+                    let sin = cos * t;
+                    rotate_columns(&mut u, (i, j), (cos, sin));
+                    rotate_columns(&mut v, (i, j), (cos, sin));
+                }
+            }
+            if !rotated {
+                converged = true;
+                break;
+            }
+        }
+
+        let norms = u
+            .axis_iter(Axis(1))
+            .map(|column| column.dot(&column).sqrt())
+            .collect::<Vec<_>>();
+        let mut order = (0..n).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| norms[b].total_cmp(&norms[a]));
+
+        let singular_values = order.iter().map(|&k| norms[k]).collect::<Array1<f64>>();
+        let mut left = Array2::zeros((m, n));
+        let mut vt = Array2::zeros((n, n));
+        for (position, &k) in order.iter().enumerate() {
+            if norms[k] != 0.0 {
+                left.column_mut(position).assign(&(&u.column(k) / norms[k]));
+            }
+            vt.row_mut(position).assign(&v.column(k));
+        }
+
+        Self {
+            u: left,
+            singular_values,
+            vt,
+            sweeps,
+            converged,
+        }
+    }
+
+    pub fn u(&self) -> ArrayView2<'_, f64> {
+        self.u.view()
+    }
+
+    pub fn singular_values(&self) -> ArrayView1<'_, f64> {
+        self.singular_values.view()
+    }
+
+    pub fn vt(&self) -> ArrayView2<'_, f64> {
+        self.vt.view()
+    }
+
+    pub fn sweeps(&self) -> u32 {
+        self.sweeps
+    }
+
+    /// Whether the last sweep found every pair of columns orthogonal.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+
+    /// `σ_max / σ_min`, infinite for a rank deficient matrix and `NaN` for an empty one.
+    pub fn condition_two(&self) -> f64 {
+        match (self.singular_values.first(), self.singular_values.last()) {
+            (_, Some(&0.0)) => f64::INFINITY,
+            (Some(&largest), Some(&smallest)) => largest / smallest,
+            _ => f64::NAN,
+        }
+    }
+
+    /// Number of singular values above `tolerance * σ_max`.
+    pub fn rank(&self, tolerance: f64) -> usize {
+        let threshold = tolerance * self.singular_values.first().copied().unwrap_or(0.0);
+        self.singular_values
+            .iter()
+            .take_while(|&&sigma| sigma > threshold && sigma != 0.0)
+            .count()
+    }
+
+    /// Moore–Penrose pseudo-inverse `V Σ⁺ Uᵀ` ignoring singular values below `tolerance * σ_max`.
+    pub fn pseudo_inverse(&self, tolerance: f64) -> Array2<f64> {
+        let rank = self.rank(tolerance);
+        let vt = self.vt.slice(s![..rank, ..]);
+        let u = self.u.slice(s![.., ..rank]);
+        let inverse_sigma = self
+            .singular_values
+            .slice(s![..rank])
+            .mapv(|sigma| 1.0 / sigma);
+        (&vt.t() * &inverse_sigma.insert_axis(Axis(0))).dot(&u.t())
+    }
+
+    /// Truncated SVD solution `Σ_{i < rank} (uᵢᵀb / σᵢ) vᵢ`,
+    /// the minimum norm least squares solution when `rank` is the numerical rank.
+    pub fn solve_truncated(&self, vector: ArrayView1<f64>, rank: usize) -> Array1<f64> {
+        let rank = rank.min(self.rank(0.0));
+        let coefficients =
+            self.u.slice(s![.., ..rank]).t().dot(&vector) / self.singular_values.slice(s![..rank]);
+        self.vt.slice(s![..rank, ..]).t().dot(&coefficients)
+    }
+}
+
+/// `(aᵢ, aⱼ) <- (c aᵢ - s aⱼ, s aᵢ + c aⱼ)`.
+fn rotate_columns(matrix: &mut Array2<f64>, (i, j): (usize, usize), (cos, sin): (f64, f64)) {
+    let (a, b) = matrix.multi_slice_mut((s![.., i], s![.., j]));
+    Zip::from(a).and(b).for_each(|a, b| {
+        let (x, y) = (*a, *b);
+        *a = cos * x - sin * y;
+        *b = sin * x + cos * y;
+    });
+}
+
+/// Solver for [`crate::Experiment::run`], minimum norm least squares solution by the Jacobi SVD.
+pub fn svd_solve(matrix: ArrayView2<f64>, vector: ArrayView1<f64>) -> Option<Array1<f64>> {
+    let svd = Svd::new(matrix);
+    if !svd.converged() {
+        return None;
+    }
+    let rank = svd.rank(f64::EPSILON * matrix.nrows().max(matrix.ncols()) as f64);
+    Some(svd.solve_truncated(vector, rank))
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, s, Array2, Axis};
+    use ndarray_linalg::SVD;
+
+    use matrices::Examples;
+
+    use crate::svd::Svd;
+
+    #[test]
+    fn reconstructs_rectangular_matrices() {
+        let tall = Examples::random_matrix(6).slice(s![.., ..4]).to_owned();
+        for matrix in [tall.clone(), tall.reversed_axes()] {
+            let svd = Svd::new(matrix.view());
+            assert!(svd.converged());
+            let (m, n) = matrix.dim();
+            let k = m.min(n);
+            assert_eq!(svd.u().dim(), (m, k));
+            assert_eq!(svd.vt().dim(), (k, n));
+
+            let sigma = svd.singular_values().insert_axis(Axis(0)).to_owned();
+            let product = (&svd.u() * &sigma).dot(&svd.vt());
+            assert!(product.abs_diff_eq(&matrix, 1e-12));
+            assert!(svd
+                .u()
+                .t()
+                .dot(&svd.u())
+                .abs_diff_eq(&Array2::eye(k), 1e-12));
+            assert!(svd
+                .vt()
+                .dot(&svd.vt().t())
+                .abs_diff_eq(&Array2::eye(k), 1e-12));
+
+            let (_, lapack, _) = matrix.svd(false, false).unwrap();
+            assert!(svd.singular_values().abs_diff_eq(&lapack, 1e-12));
+        }
+    }
+
+    #[test]
+    fn pseudo_inverse_of_rank_deficient_matrix() {
+        // rank one, A⁺ = Aᵀ / ||A||²_F
+        let matrix = array![[1.0, 2.0], [2.0, 4.0], [3.0, 6.0]];
+        let svd = Svd::new(matrix.view());
+
+        assert_eq!(svd.rank(1e-12), 1);
+        assert!(svd.condition_two() > 1e15);
+        let expected = matrix.t().to_owned() / 70.0;
+        assert!(svd.pseudo_inverse(1e-12).abs_diff_eq(&expected, 1e-14));
+
+        let vector = array![1.0, 1.0, 1.0];
+        let x = svd.solve_truncated(vector.view(), 1);
+        assert!(x.abs_diff_eq(&expected.dot(&vector), 1e-14));
+    }
+}
@@ -0,0 +1,328 @@
+use ndarray::{ArrayView1, ArrayView2};
+use ndarray_linalg::Norm;
+
+use matrices::Observer;
+
+use crate::error::{normalized, EigenError};
+use crate::inverse::shifted;
+use crate::lanczos::ritz_values;
+use crate::{
+    converged, posterior_error, power_iteration, power_iteration_observed, EigenvalueResult,
+    Iteration, MAX_STEPS,
+};
+
+/// Lanczos steps spent on the spectrum estimate of [`Acceleration::Shift`] and [`Acceleration::Chebyshev`].
+const ESTIMATION_STEPS: usize = 20;
+/// The smallest Ritz value lies above `λ_n` and Chebyshev polynomials grow fast below their interval,
+/// so the estimate of `λ_n` is moved down by this part of the estimated spectrum width.
+const SAFETY: f64 = 0.05;
+
+/// Accelerations of the power iteration for symmetric matrices.
+///
+/// All of them rely on the symmetry: the Rayleigh quotients converge at the squared rate
+/// and the spectrum `[λ_n, λ_1]` estimated by Lanczos steps is real.
+/// A nonsymmetric matrix is not rejected, but the results are meaningless.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Acceleration {
+    /// Aitken's Δ² extrapolation `λ̂_k = λ_k - (Δλ_k)² / Δ²λ_k` of the Rayleigh quotients,
+    /// converged when `|λ̂_k - λ̂_{k-1}| <= epsilon`.
+    /// Only the eigenvalue is accelerated: the eigenvector is the plain iterate `x_k` and the error
+    /// is its residual `||A x_k - λ̂_k x_k||`, which bounds the distance of `λ̂_k`
+    /// to the nearest eigenvalue but usually stays above `epsilon`.
+    /// A start vector nearly orthogonal to the dominant eigenvector can stall the quotients
+    /// at `λ_2` long enough to stop there.
+    Aitken,
+    /// Power iteration for `A - σI` with the optimal `σ = (λ_2 + λ_n) / 2`,
+    /// the rate `λ_2 / λ_1` becomes `(λ_2 - λ_n) / (2 λ_1 - λ_2 - λ_n)`.
+    Shift,
+    /// `x_k = T_k((A - cI) / e) x_0 / T_k((λ_1 - c) / e)` for the Chebyshev polynomials `T_k`
+    /// on `[λ_n, λ_2] = [c - e, c + e]`, where they stay below one while growing fastest outside.
+    Chebyshev,
+}
+
+/// Largest, second largest and smallest eigenvalue estimates of a symmetric matrix.
+struct Spectrum {
+    largest: f64,
+    second: f64,
+    smallest: f64,
+    /// Lanczos steps spent.
+    steps: u32,
+}
+
+impl Spectrum {
+    /// From the unit `vector`.
+    fn estimate(matrix: ArrayView2<f64>, vector: ArrayView1<f64>) -> Self {
+        let values = ritz_values(matrix, vector, ESTIMATION_STEPS);
+        let m = values.len();
+        let (largest, smallest) = (values[m - 1], values[0]);
+        Self {
+            largest,
+            second: values[m.saturating_sub(2)],
+            smallest: smallest - SAFETY * (largest - smallest),
+            steps: m as u32,
+        }
+    }
+}
+
+/// The largest eigenvalue of a symmetric matrix by power iteration with `acceleration`,
+/// the symmetry is assumed and not checked.
+///
+/// [`Acceleration::Shift`] and [`Acceleration::Chebyshev`] estimate the spectrum
+/// by a few Lanczos steps, which are counted in the steps.
+/// Stops when converged or after [`MAX_STEPS`] steps, a zero `vector` is an error.
+pub fn accelerated_power_iteration(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    acceleration: Acceleration,
+    epsilon: f64,
+) -> Result<EigenvalueResult, EigenError> {
+    accelerated_power_iteration_observed(matrix, vector, acceleration, epsilon, &mut ())
+}
+
+/// [`accelerated_power_iteration`] reporting every step to `observer`,
+/// the Lanczos steps are counted but not reported.
+pub fn accelerated_power_iteration_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    acceleration: Acceleration,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    match acceleration {
+        Acceleration::Aitken => aitken(matrix, vector, epsilon, observer),
+        Acceleration::Shift => shifted_power_iteration(matrix, vector, epsilon, observer),
+        Acceleration::Chebyshev => chebyshev(matrix, vector, epsilon, observer),
+    }
+}
+
+/// [`accelerated_power_iteration`] next to a plain [`power_iteration`] from the same vector.
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Comparison {
+    pub accelerated: EigenvalueResult,
+    pub plain: EigenvalueResult,
+}
+
+impl Comparison {
+    /// Steps saved by the acceleration, negative when it took longer.
+    pub fn saved_steps(&self) -> i64 {
+        i64::from(self.plain.steps) - i64::from(self.accelerated.steps)
+    }
+}
+
+/// Runs [`accelerated_power_iteration`] and a plain [`power_iteration`] from the same vector.
+pub fn compare_with_power_iteration(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    acceleration: Acceleration,
+    epsilon: f64,
+) -> Result<Comparison, EigenError> {
+    Ok(Comparison {
+        accelerated: accelerated_power_iteration(matrix, vector, acceleration, epsilon)?,
+        plain: power_iteration(matrix, vector, epsilon)?,
+    })
+}
+
+/// Reports the extrapolated eigenvalue, the Rayleigh quotient for the first step,
+/// with the residual of the iterate.
+fn aitken(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let mut x = normalized(vector)?;
+    let mut product = matrix.dot(&x);
+    // the last three Rayleigh quotients
+    let mut quotients = [f64::NAN, f64::NAN, x.dot(&product)];
+    let mut extrapolated = None;
+    let mut change = f64::INFINITY;
+    let mut steps = 0;
+    while change > epsilon && steps < MAX_STEPS {
+        x = &product / product.norm();
+        product = matrix.dot(&x);
+        steps += 1;
+        quotients.rotate_left(1);
+        quotients[2] = x.dot(&product);
+        if steps < 2 {
+            observer.observe(Iteration {
+                step: steps,
+                eigenvalue: quotients[2],
+                error: posterior_error(x.view(), product.view(), quotients[2]),
+            });
+            continue;
+        }
+
+        let [a, b, c] = quotients;
+        let denominator = c - 2.0 * b + a;
+        let next = if denominator == 0.0 {
+            c
+        } else {
+            c - (c - b).powi(2) / denominator
+        };
+        if let Some(previous) = extrapolated {
+            change = f64::abs(next - previous);
+        }
+        extrapolated = Some(next);
+        observer.observe(Iteration {
+            step: steps,
+            eigenvalue: next,
+            error: posterior_error(x.view(), product.view(), next),
+        });
+    }
+
+    let eigenvalue = extrapolated.unwrap_or(quotients[2]);
+    Ok(EigenvalueResult {
+        eigenvalue,
+        error: posterior_error(x.view(), product.view(), eigenvalue),
+        eigenvector: x,
+        steps,
+        convergence: converged(change, epsilon),
+    })
+}
+
+fn shifted_power_iteration(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let x = normalized(vector)?;
+    let spectrum = Spectrum::estimate(matrix, x.view());
+    let shift = (spectrum.second + spectrum.smallest) / 2.0;
+
+    // ||(A - σI) x - μ x|| = ||A x - (μ + σ) x||, the error carries over
+    let mut result = power_iteration_observed(
+        shifted(matrix, shift).view(),
+        x.view(),
+        epsilon,
+        &mut |iteration: Iteration| {
+            observer.observe(Iteration {
+                step: iteration.step + spectrum.steps,
+                eigenvalue: iteration.eigenvalue + shift,
+                ..iteration
+            })
+        },
+    )?;
+    result.eigenvalue += shift;
+    result.steps += spectrum.steps;
+    Ok(result)
+}
+
+/// Saad's three-term recurrence with `σ_1 = e / (λ_1 - c)` and `σ_{k+1} = 1 / (2 / σ_1 - σ_k)`:
+/// `x_{k+1} = 2 σ_{k+1} / e (A - cI) x_k - σ_k σ_{k+1} x_{k-1}`.
+/// Both iterates are rescaled together, which keeps the recurrence linear.
+fn chebyshev(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let mut previous = normalized(vector)?;
+    let spectrum = Spectrum::estimate(matrix, previous.view());
+    let center = (spectrum.second + spectrum.smallest) / 2.0;
+    let half = (spectrum.second - spectrum.smallest) / 2.0;
+    if half <= 0.0 || spectrum.largest <= spectrum.second {
+        return shifted_power_iteration(matrix, vector, epsilon, observer);
+    }
+
+    let first = half / (spectrum.largest - center);
+    let mut sigma = first;
+    let mut x = (matrix.dot(&previous) - center * &previous) * (first / half);
+    let mut steps = 0;
+    loop {
+        let norm = x.norm();
+        x /= norm;
+        previous /= norm;
+        let product = matrix.dot(&x);
+        let eigenvalue = x.dot(&product);
+        let error = posterior_error(x.view(), product.view(), eigenvalue);
+        if steps > 0 {
+            observer.observe(Iteration {
+                step: steps + spectrum.steps,
+                eigenvalue,
+                error,
+            });
+        }
+        if error <= epsilon || steps == MAX_STEPS {
+            return Ok(EigenvalueResult {
+                eigenvalue,
+                eigenvector: x,
+                error,
+                steps: steps + spectrum.steps,
+                convergence: converged(error, epsilon),
+            });
+        }
+
+        let next_sigma = 1.0 / (2.0 / first - sigma);
+        let next =
+            (product - center * &x) * (2.0 * next_sigma / half) - &previous * (sigma * next_sigma);
+        previous = x;
+        x = next;
+        sigma = next_sigma;
+        steps += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array1, Array2, Axis};
+
+    use matrices::Examples;
+
+    use crate::{
+        accelerated_power_iteration, compare_with_power_iteration, Acceleration, Convergence,
+        EigenError,
+    };
+
+    /// `H D H` for a Householder reflection `H`, eigenvalues `1, 0.99` and the rest in `[0, 0.9]`,
+    /// so plain power iteration converges at the rate `0.99`,
+    /// and the start vector with unit components along every eigenvector.
+    fn slow(n: usize) -> (Array2<f64>, Array1<f64>) {
+        let mut spectrum = (0..n - 2)
+            .map(|i| 0.9 * i as f64 / (n - 3) as f64)
+            .collect::<Vec<_>>();
+        spectrum.extend([1.0, 0.99]);
+
+        let u = Examples::random_vector(n);
+        let column = u.view().insert_axis(Axis(1));
+        let reflection = Array2::eye(n) - 2.0 * column.dot(&column.t()) / u.dot(&u);
+        let matrix = reflection
+            .dot(&Array2::from_diag(&Array1::from(spectrum)))
+            .dot(&reflection);
+        let vector = reflection.dot(&Array1::ones(n));
+        (matrix, vector)
+    }
+
+    #[test]
+    fn accelerations_save_steps() {
+        let (matrix, vector) = slow(40);
+
+        for (acceleration, saved) in [
+            (Acceleration::Aitken, 1000),
+            (Acceleration::Shift, 500),
+            (Acceleration::Chebyshev, 1000),
+        ] {
+            let comparison =
+                compare_with_power_iteration(matrix.view(), vector.view(), acceleration, 1e-10)
+                    .unwrap();
+            let result = &comparison.accelerated;
+            assert_eq!(result.convergence, Convergence::Converged);
+            // the residual bounds the eigenvalue error of a symmetric matrix
+            assert!((result.eigenvalue - 1.0).abs() <= result.error);
+            assert!(comparison.saved_steps() > saved);
+        }
+
+        let zero = Array1::zeros(40);
+        for acceleration in [
+            Acceleration::Aitken,
+            Acceleration::Shift,
+            Acceleration::Chebyshev,
+        ] {
+            let error =
+                accelerated_power_iteration(matrix.view(), zero.view(), acceleration, 1e-10);
+            assert!(matches!(error, Err(EigenError::ZeroVector)));
+        }
+    }
+}
@@ -0,0 +1,315 @@
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2};
+use ndarray_linalg::{c64, Eig, Norm};
+
+use matrices::Observer;
+
+use crate::error::{normalized, EigenError};
+use crate::EigenpairsIteration;
+
+const MAX_RESTARTS: u32 = 300;
+/// Lower bound on the number of Arnoldi vectors, as in ARPACK drivers.
+const MIN_BASIS: usize = 20;
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct ArnoldiResult {
+    /// Ritz values by decreasing magnitude, `a + bi` before `a - bi`.
+    pub eigenvalues: Vec<c64>,
+    /// `||A y - θ y|| = ||f_m|| |e_mᵀ s|` for the unit Ritz vector `y = V_m s`.
+    pub residuals: Vec<f64>,
+    pub restarts: u32,
+    /// Applications of the operator.
+    pub steps: u32,
+}
+
+/// Arnoldi factorization `A V_j = V_j H_j + f_j e_jᵀ` with `j <= m`.
+struct Factorization {
+    basis: Vec<Array1<f64>>,
+    hessenberg: Array2<f64>,
+    residual: Array1<f64>,
+    steps: u32,
+}
+
+impl Factorization {
+    fn new(vector: ArrayView1<f64>, m: usize) -> Self {
+        Self {
+            basis: Vec::with_capacity(m),
+            hessenberg: Array2::zeros((m, m)),
+            residual: vector.to_owned(),
+            steps: 0,
+        }
+    }
+
+    /// Extends the factorization to `m` columns by classical Gram–Schmidt with reorthogonalization.
+    /// `false` when `f_j` vanishes, then the span of `V_j` is invariant and `H_j` is exact.
+    fn extend<F>(&mut self, operator: &F, m: usize) -> bool
+    where
+        F: Fn(ArrayView1<f64>) -> Array1<f64>,
+    {
+        while self.basis.len() < m {
+            let j = self.basis.len();
+            let beta = self.residual.norm();
+            if j > 0 && beta <= f64::EPSILON * self.hessenberg.norm_max() {
+                return false;
+            }
+            if j > 0 {
+                self.hessenberg[(j, j - 1)] = beta;
+            }
+            self.basis.push(&self.residual / beta);
+
+            let mut w = operator(self.basis[j].view());
+            self.steps += 1;
+            for _ in 0..2 {
+                for (i, v) in self.basis.iter().enumerate() {
+                    let projection = v.dot(&w);
+                    self.hessenberg[(i, j)] += projection;
+                    w.scaled_add(-projection, v);
+                }
+            }
+            self.residual = w;
+        }
+        true
+    }
+
+    /// Applies the shifts with `H <- Qᵀ H Q`, `V <- V Q` and keeps the first `kept` columns:
+    /// `f_kept = v_{kept+1} h_{kept+1,kept} + f_m q_{m,kept}` (Sorensen's implicit restart).
+    fn restart(&mut self, shifts: &[c64], kept: usize) {
+        let m = self.basis.len();
+        let mut q = Array2::<f64>::eye(m);
+        for shift in shifts {
+            let hessenberg = self.hessenberg.slice(s![..m, ..m]).to_owned();
+            let polynomial = if shift.im == 0.0 {
+                &hessenberg - &(Array2::<f64>::eye(m) * shift.re)
+            } else if shift.im > 0.0 {
+                // (H - μ)(H - μ̄) is real
+                hessenberg.dot(&hessenberg) - 2.0 * shift.re * &hessenberg
+                    + Array2::<f64>::eye(m) * shift.norm_sqr()
+            } else {
+                continue;
+            };
+            let factor = orthogonal_factor(polynomial.view());
+            let mut shifted = factor.t().dot(&hessenberg).dot(&factor);
+            for ((i, j), h) in shifted.indexed_iter_mut() {
+                if i > j + 1 {
+                    *h = 0.0;
+                }
+            }
+            self.hessenberg.slice_mut(s![..m, ..m]).assign(&shifted);
+            q = q.dot(&factor);
+        }
+
+        let rotated = (0..=kept.min(m - 1))
+            .map(|i| {
+                self.basis
+                    .iter()
+                    .zip(q.column(i))
+                    .fold(Array1::zeros(self.residual.len()), |v, (b, q)| v + *q * b)
+            })
+            .collect::<Vec<_>>();
+        let mut residual = &self.residual * q[(m - 1, kept - 1)];
+        if kept < m {
+            residual.scaled_add(self.hessenberg[(kept, kept - 1)], &rotated[kept]);
+        }
+
+        let mut hessenberg = Array2::zeros((self.hessenberg.nrows(), self.hessenberg.ncols()));
+        hessenberg
+            .slice_mut(s![..kept, ..kept])
+            .assign(&self.hessenberg.slice(s![..kept, ..kept]));
+        self.hessenberg = hessenberg;
+        self.basis = rotated.into_iter().take(kept).collect();
+        self.residual = residual;
+    }
+}
+
+/// `Q` of `M = QR` by Givens rotations.
+fn orthogonal_factor(matrix: ArrayView2<f64>) -> Array2<f64> {
+    let m = matrix.nrows();
+    let mut r = matrix.to_owned();
+    let mut q = Array2::<f64>::eye(m);
+    for j in 0..m {
+        for i in j + 1..m {
+            let (a, b) = (r[(j, j)], r[(i, j)]);
+            if b == 0.0 {
+                continue;
+            }
+            let radius = a.hypot(b);
+            let (cos, sin) = (a / radius, b / radius);
+            for column in 0..m {
+                let (x, y) = (r[(j, column)], r[(i, column)]);
+                r[(j, column)] = cos * x + sin * y;
+                r[(i, column)] = -sin * x + cos * y;
+            }
+            for row in 0..m {
+                let (x, y) = (q[(row, j)], q[(row, i)]);
+                q[(row, j)] = cos * x + sin * y;
+                q[(row, i)] = -sin * x + cos * y;
+            }
+        }
+    }
+    q
+}
+
+/// `k` eigenvalues of largest magnitude of the operator `x -> A x` on vectors of the length
+/// of `vector` by the implicitly restarted Arnoldi method with `m = min(max(2k + 1, 20), n)` columns.
+///
+/// One more eigenvalue is returned when the `k`-th one is half of a complex conjugate pair.
+/// Stops when every residual estimate is below `epsilon`, on an invariant subspace
+/// or after `MAX_RESTARTS` restarts.
+/// Needs `k >= 1` and a nonzero `vector`.
+pub fn arnoldi<F>(
+    operator: F,
+    vector: ArrayView1<f64>,
+    k: usize,
+    epsilon: f64,
+) -> Result<ArnoldiResult, EigenError>
+where
+    F: Fn(ArrayView1<f64>) -> Array1<f64>,
+{
+    arnoldi_observed(operator, vector, k, epsilon, &mut ())
+}
+
+/// [`arnoldi`] reporting the wanted Ritz values and their residual estimates
+/// before every restart and at the end to `observer`, the step counts operator applications.
+pub fn arnoldi_observed<F>(
+    operator: F,
+    vector: ArrayView1<f64>,
+    k: usize,
+    epsilon: f64,
+    observer: &mut impl Observer<EigenpairsIteration<c64>>,
+) -> Result<ArnoldiResult, EigenError>
+where
+    F: Fn(ArrayView1<f64>) -> Array1<f64>,
+{
+    if k == 0 {
+        return Err(EigenError::NothingWanted);
+    }
+    let vector = normalized(vector)?;
+    let n = vector.len();
+    let k = k.min(n);
+    let m = (2 * k + 1).max(MIN_BASIS).min(n);
+
+    let mut factorization = Factorization::new(vector.view(), m);
+    let mut restarts = 0;
+    loop {
+        let complete = factorization.extend(&operator, m);
+        let size = factorization.basis.len();
+        // a 1 × 1 slice copied by `to_owned` has zero strides, which LAPACK rejects
+        let hessenberg =
+            Array2::from_shape_fn((size, size), |index| factorization.hessenberg[index]);
+        let (values, vectors) = hessenberg.eig()?;
+
+        let mut order = (0..size).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            let (a, b) = (values[a], values[b]);
+            b.norm().total_cmp(&a.norm()).then(b.im.total_cmp(&a.im))
+        });
+        let mut wanted = k.min(size);
+        if wanted < size && values[order[wanted - 1]].im > 0.0 {
+            wanted += 1;
+        }
+
+        let norm = factorization.residual.norm();
+        let residuals = order[..wanted]
+            .iter()
+            .map(|&i| {
+                let s = vectors.column(i);
+                norm * s[size - 1].norm() / s.norm_l2()
+            })
+            .collect::<Vec<_>>();
+        let eigenvalues = order[..wanted]
+            .iter()
+            .map(|&i| values[i])
+            .collect::<Vec<_>>();
+        observer.observe(EigenpairsIteration {
+            step: factorization.steps,
+            eigenvalues: eigenvalues.clone(),
+            errors: residuals.clone(),
+        });
+
+        let converged = residuals.iter().all(|&residual| residual < epsilon);
+        if converged || !complete || size == n || restarts == MAX_RESTARTS {
+            return Ok(ArnoldiResult {
+                eigenvalues,
+                residuals,
+                restarts,
+                steps: factorization.steps,
+            });
+        }
+
+        let shifts = order[wanted..]
+            .iter()
+            .map(|&i| values[i])
+            .collect::<Vec<_>>();
+        factorization.restart(&shifts, wanted);
+        restarts += 1;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array1, Array2, ArrayView1, Axis};
+    use ndarray_linalg::c64;
+
+    use matrices::Examples;
+
+    use crate::{arnoldi, EigenError};
+
+    /// `H B H` for a Householder reflection `H` and a block upper triangular `B`
+    /// with eigenvalues `5, -4, 3 ± 2i` and the rest in `[0, 3)`.
+    fn nonsymmetric(n: usize) -> Array2<f64> {
+        let block = Array2::from_shape_fn((n, n), |(i, j)| match (i, j) {
+            (0, 0) => 5.0,
+            (1, 1) => -4.0,
+            (2, 2) | (3, 3) => 3.0,
+            (2, 3) => -2.0,
+            (3, 2) => 2.0,
+            (i, j) if i == j => 3.0 * (i - 4) as f64 / n as f64,
+            (i, j) if i < j => 0.01,
+            _ => 0.0,
+        });
+
+        let u = Examples::random_vector(n);
+        let column = u.view().insert_axis(Axis(1));
+        let reflection = Array2::eye(n) - 2.0 * column.dot(&column.t()) / u.dot(&u);
+        reflection.dot(&block).dot(&reflection)
+    }
+
+    #[test]
+    fn largest_eigenvalues_with_complex_pair() {
+        let matrix = nonsymmetric(100);
+        let vector = Examples::random_vector(100);
+        let operator = |x: ArrayView1<f64>| matrix.dot(&x);
+        let result = arnoldi(operator, vector.view(), 3, 1e-10).unwrap();
+
+        let expected = [
+            c64::new(5.0, 0.0),
+            c64::new(-4.0, 0.0),
+            c64::new(3.0, 2.0),
+            c64::new(3.0, -2.0),
+        ];
+        assert_eq!(result.eigenvalues.len(), 4);
+        for (lambda, expected) in result.eigenvalues.iter().zip(expected) {
+            assert!((lambda - expected).norm() < 1e-9);
+        }
+        assert!(result.residuals.iter().all(|&r| r < 1e-10));
+        assert!(result.restarts > 0);
+
+        let zero = Array1::zeros(100);
+        let error = arnoldi(operator, zero.view(), 3, 1e-10);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+        let error = arnoldi(operator, vector.view(), 0, 1e-10);
+        assert!(matches!(error, Err(EigenError::NothingWanted)));
+    }
+
+    #[test]
+    fn eigenvector_start_spans_invariant_subspace() {
+        let matrix = Array2::from_diag(&Array1::from(vec![2.0, 1.0, 0.5]));
+        let vector = Array1::from(vec![1.0, 0.0, 0.0]);
+        let operator = |x: ArrayView1<f64>| matrix.dot(&x);
+        let result = arnoldi(operator, vector.view(), 1, 1e-12).unwrap();
+
+        assert_eq!(result.eigenvalues, [c64::new(2.0, 0.0)]);
+        assert_eq!(result.residuals, [0.0]);
+        assert_eq!(result.steps, 1);
+    }
+}
@@ -0,0 +1,346 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2, Axis};
+use ndarray_linalg::Norm;
+
+use eigenvalues_problem::cyclic_jacobi_method;
+use matrices::Observer;
+
+use crate::error::{normalized, EigenError};
+use crate::lanczos::orthogonalize;
+use crate::{
+    converged, power_iteration_observed, Convergence, EigenpairsIteration, Iteration,
+    PairIteration, MAX_STEPS,
+};
+
+/// Jacobi tolerance for the projected matrix relative to its norm.
+const PROJECTED_TOLERANCE: f64 = 1e-14;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Deflation {
+    /// `A_{i+1} = A_i - λ_i x_i x_iᵀ` for the unit `x_i`, for symmetric matrices.
+    Hotelling,
+    /// `A_{i+1} = A_i - λ_i x_i zᵀ` with `z` the row of `A_i` at the largest component of `x_i`
+    /// scaled to `zᵀ x_i = 1`, that row of `A_{i+1}` vanishes.
+    Wielandt,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EigenpairsResult {
+    /// By decreasing magnitude.
+    pub eigenvalues: Vec<f64>,
+    /// Unit column `i` belongs to `eigenvalues[i]`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::matrix"))]
+    pub eigenvectors: Array2<f64>,
+    /// `||A x_i - λ_i x_i||` for the original matrix, the errors of the earlier pairs
+    /// are carried into the deflated matrices and show up in the later ones.
+    pub errors: Vec<f64>,
+    /// Iterations spent on each pair, the total for every pair of a subspace iteration.
+    pub steps: Vec<u32>,
+    pub convergence: Vec<Convergence>,
+}
+
+/// `k` dominant eigenpairs found one after another by [`power_iteration`](crate::power_iteration)
+/// on deflated matrices, each iteration is started from `vector`
+/// and limited to [`MAX_STEPS`] steps.
+///
+/// Both deflations are `A_{i+1} = A_i - λ_i x_i zᵀ` with `zᵀ x_i = 1`, so an eigenvector `w`
+/// of `A_{i+1}` for `λ` gives `w + λ_i (zᵀ w) / (λ - λ_i) x_i` of `A_i`.
+/// For `|λ - λ_i| <= epsilon` the eigenvalue is taken as repeated and `w` is kept.
+///
+/// Fewer than `k` pairs are returned when a power iteration does not converge,
+/// its wrong `λ_i` would spoil every later pair, or when `λ_i = 0`, all the rest are zero.
+/// A zero `vector` is an error.
+pub fn deflation(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    k: usize,
+    deflation: Deflation,
+    epsilon: f64,
+) -> Result<EigenpairsResult, EigenError> {
+    deflation_observed(matrix, vector, k, deflation, epsilon, &mut ())
+}
+
+/// [`deflation`] reporting every step of every power iteration to `observer`.
+pub fn deflation_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    k: usize,
+    deflation: Deflation,
+    epsilon: f64,
+    observer: &mut impl Observer<PairIteration>,
+) -> Result<EigenpairsResult, EigenError> {
+    let vector = normalized(vector)?;
+    let n = matrix.nrows();
+    let k = k.min(n);
+
+    let mut deflated = matrix.to_owned();
+    // (λ_i, x_i, z) of the applied deflations
+    let mut stages: Vec<(f64, Array1<f64>, Array1<f64>)> = Vec::with_capacity(k);
+    let mut eigenvalues = Vec::with_capacity(k);
+    let mut columns = Vec::with_capacity(k);
+    let mut steps = Vec::with_capacity(k);
+    let mut convergence = Vec::with_capacity(k);
+    for pair in 0..k {
+        let result = power_iteration_observed(
+            deflated.view(),
+            vector.view(),
+            epsilon,
+            &mut |iteration: Iteration| {
+                observer.observe(PairIteration {
+                    pair,
+                    step: iteration.step,
+                    eigenvalue: iteration.eigenvalue,
+                    error: iteration.error,
+                })
+            },
+        )?;
+        let (eigenvalue, x) = (result.eigenvalue, result.eigenvector);
+
+        let mut eigenvector = x.clone();
+        for (lambda, x, z) in stages.iter().rev() {
+            if (eigenvalue - lambda).abs() <= epsilon {
+                continue;
+            }
+            let coefficient = lambda * z.dot(&eigenvector) / (eigenvalue - lambda);
+            eigenvector.scaled_add(coefficient, x);
+        }
+        // the correction cancels `w` only if it is parallel to `x_i`, then `x_i` is kept
+        columns.push(normalized(eigenvector.view()).unwrap_or_else(|_| x.clone()));
+        eigenvalues.push(eigenvalue);
+        steps.push(result.steps);
+        convergence.push(result.convergence);
+        if result.convergence != Convergence::Converged || eigenvalue == 0.0 {
+            break;
+        }
+
+        let z = match deflation {
+            Deflation::Hotelling => x.clone(),
+            Deflation::Wielandt => {
+                let r = (0..n)
+                    .max_by(|&a, &b| x[a].abs().total_cmp(&x[b].abs()))
+                    .unwrap();
+                deflated.row(r).to_owned() / (eigenvalue * x[r])
+            }
+        };
+        deflated -= &(eigenvalue
+            * &x.view()
+                .insert_axis(Axis(1))
+                .dot(&z.view().insert_axis(Axis(0))));
+        stages.push((eigenvalue, x, z));
+    }
+
+    let eigenvectors = Array2::from_shape_fn((n, columns.len()), |(i, j)| columns[j][i]);
+    Ok(EigenpairsResult {
+        errors: residuals(matrix, &eigenvalues, eigenvectors.view()),
+        eigenvalues,
+        eigenvectors,
+        steps,
+        convergence,
+    })
+}
+
+/// Dominant eigenpairs of a symmetric matrix by orthogonal (subspace) iteration
+/// of the block `vectors`, one pair per column: `Q R = A Y_s` and the Ritz pairs
+/// `Y_{s+1} = Q S` of `Qᵀ A Q = S Θ Sᵀ`.
+///
+/// Stops when every residual is below `epsilon` or after [`MAX_STEPS`] steps.
+/// The block needs at most `n` columns, and `A Y_s` must keep full column rank.
+pub fn subspace_iteration(
+    matrix: ArrayView2<f64>,
+    vectors: ArrayView2<f64>,
+    epsilon: f64,
+) -> Result<EigenpairsResult, EigenError> {
+    subspace_iteration_observed(matrix, vectors, epsilon, &mut ())
+}
+
+/// [`subspace_iteration`] reporting the Ritz pairs after every step to `observer`.
+pub fn subspace_iteration_observed(
+    matrix: ArrayView2<f64>,
+    vectors: ArrayView2<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<EigenpairsIteration>,
+) -> Result<EigenpairsResult, EigenError> {
+    let k = vectors.ncols();
+    if k > matrix.nrows() {
+        return Err(EigenError::TooManyVectors {
+            vectors: k,
+            dimension: matrix.nrows(),
+        });
+    }
+    let mut block = vectors.to_owned();
+    let mut steps = 0;
+    loop {
+        let basis = orthonormal(matrix.dot(&block).view())?;
+        let projected = basis.t().dot(&matrix.dot(&basis));
+        let projected = (&projected + &projected.t()) / 2.0;
+        let result =
+            cyclic_jacobi_method(projected.view(), PROJECTED_TOLERANCE * projected.norm_max());
+
+        let values = result.eigenvalues();
+        let mut order = (0..k).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| values[b].abs().total_cmp(&values[a].abs()));
+        let eigenvalues = order.iter().map(|&i| values[i]).collect::<Vec<_>>();
+        block = basis.dot(&result.eigenvectors().select(Axis(1), &order));
+        steps += 1;
+
+        let errors = residuals(matrix, &eigenvalues, block.view());
+        observer.observe(EigenpairsIteration {
+            step: steps,
+            eigenvalues: eigenvalues.clone(),
+            errors: errors.clone(),
+        });
+        if errors.iter().all(|&error| error < epsilon) || steps == MAX_STEPS {
+            return Ok(EigenpairsResult {
+                convergence: errors
+                    .iter()
+                    .map(|&error| converged(error, epsilon))
+                    .collect(),
+                eigenvalues,
+                eigenvectors: block,
+                errors,
+                steps: vec![steps; k],
+            });
+        }
+    }
+}
+
+/// `Q` of `block = QR` by Gram–Schmidt with reorthogonalization.
+///
+/// A column that loses all but rounding noise to the earlier ones makes the block rank deficient.
+fn orthonormal(block: ArrayView2<f64>) -> Result<Array2<f64>, EigenError> {
+    let mut columns: Vec<Array1<f64>> = Vec::with_capacity(block.ncols());
+    for column in block.columns() {
+        let mut q = column.to_owned();
+        orthogonalize(&mut q, &columns);
+        let norm = q.norm();
+        if norm <= f64::EPSILON * column.norm() {
+            return Err(EigenError::RankDeficient);
+        }
+        columns.push(q / norm);
+    }
+    Ok(Array2::from_shape_fn(
+        (block.nrows(), columns.len()),
+        |(i, j)| columns[j][i],
+    ))
+}
+
+/// `||A x_i - λ_i x_i||` for the unit columns `x_i`.
+fn residuals(
+    matrix: ArrayView2<f64>,
+    eigenvalues: &[f64],
+    eigenvectors: ArrayView2<f64>,
+) -> Vec<f64> {
+    let product = matrix.dot(&eigenvectors);
+    eigenvalues
+        .iter()
+        .enumerate()
+        .map(|(i, lambda)| (&product.column(i) - &(&eigenvectors.column(i) * *lambda)).norm())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, s, Array1, Array2, Axis};
+
+    use matrices::Examples;
+
+    use crate::{deflation, subspace_iteration, Convergence, Deflation, EigenError};
+
+    /// `H B H` for a Householder reflection `H` and a triangular `B` with eigenvalues `8, 4, 2`
+    /// and the rest in `[0, 1)`, symmetric for a diagonal `B`.
+    fn dominant(n: usize, coupling: f64) -> Array2<f64> {
+        let block = Array2::from_shape_fn((n, n), |(i, j)| match (i, j) {
+            (0, 0) => 8.0,
+            (1, 1) => 4.0,
+            (2, 2) => 2.0,
+            (i, j) if i == j => (i - 3) as f64 / n as f64,
+            (i, j) if i < j => coupling,
+            _ => 0.0,
+        });
+
+        let u = Examples::random_vector(n);
+        let column = u.view().insert_axis(Axis(1));
+        let reflection = Array2::eye(n) - 2.0 * column.dot(&column.t()) / u.dot(&u);
+        reflection.dot(&block).dot(&reflection)
+    }
+
+    #[test]
+    fn deflation_finds_dominant_eigenpairs() {
+        let symmetric = dominant(30, 0.0);
+        let nonsymmetric = dominant(30, 0.1);
+        let vector = Examples::random_vector(30);
+
+        for (matrix, method) in [
+            (&symmetric, Deflation::Hotelling),
+            (&symmetric, Deflation::Wielandt),
+            (&nonsymmetric, Deflation::Wielandt),
+        ] {
+            let result = deflation(matrix.view(), vector.view(), 3, method, 1e-12).unwrap();
+            for (lambda, expected) in result.eigenvalues.iter().zip([8.0, 4.0, 2.0]) {
+                assert!((lambda - expected).abs() < 1e-10);
+            }
+            assert!(result.errors.iter().all(|&error| error < 1e-9));
+            assert_eq!(result.steps.len(), 3);
+            assert_eq!(result.convergence, vec![Convergence::Converged; 3]);
+        }
+    }
+
+    #[test]
+    fn deflation_stops_on_failures_and_zeros() {
+        let vector = array![1.0, 0.5, 0.25];
+
+        // ±1 dominate, the power iteration oscillates
+        let pair = array![[0.0, 1.0, 0.0], [1.0, 0.0, 0.0], [0.0, 0.0, 0.5]];
+        let result = deflation(pair.view(), vector.view(), 3, Deflation::Wielandt, 1e-12).unwrap();
+        assert_eq!(result.convergence, vec![Convergence::Oscillating]);
+        assert_eq!(result.eigenvectors.ncols(), 1);
+
+        // nilpotent, λ_1 = 0 leaves nothing to deflate
+        let nilpotent = array![[0.0, 1.0, 0.0], [0.0, 0.0, 1.0], [0.0, 0.0, 0.0]];
+        let result = deflation(
+            nilpotent.view(),
+            vector.view(),
+            3,
+            Deflation::Wielandt,
+            1e-12,
+        )
+        .unwrap();
+        assert_eq!(result.eigenvalues, vec![0.0]);
+        assert!(result.eigenvectors.iter().all(|a| a.is_finite()));
+
+        let zero = Array1::zeros(3);
+        let error = deflation(pair.view(), zero.view(), 3, Deflation::Hotelling, 1e-12);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+    }
+
+    #[test]
+    fn subspace_iteration_matches_deflation() {
+        let matrix = dominant(30, 0.0);
+        let vectors = Examples::random_matrix(30);
+        let result = subspace_iteration(matrix.view(), vectors.slice(s![.., ..3]), 1e-10).unwrap();
+
+        for (lambda, expected) in result.eigenvalues.iter().zip([8.0, 4.0, 2.0]) {
+            assert!((lambda - expected).abs() < 1e-12);
+        }
+        assert!(result.errors.iter().all(|&error| error < 1e-10));
+        let vectors = &result.eigenvectors;
+        assert!(vectors.t().dot(vectors).abs_diff_eq(&Array2::eye(3), 1e-12));
+
+        let wide = Array2::ones((30, 31));
+        let error = subspace_iteration(matrix.view(), wide.view(), 1e-10);
+        assert!(matches!(
+            error,
+            Err(EigenError::TooManyVectors {
+                vectors: 31,
+                dimension: 30
+            })
+        ));
+
+        // equal columns, and a zero block, span less than their number of dimensions
+        for block in [Array2::ones((30, 2)), Array2::zeros((30, 2))] {
+            let error = subspace_iteration(matrix.view(), block.view(), 1e-10);
+            assert!(matches!(error, Err(EigenError::RankDeficient)));
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+
+use ndarray::{Array1, ArrayView1};
+use ndarray_linalg::error::LinalgError;
+use ndarray_linalg::Norm;
+
+#[derive(Debug)]
+pub enum EigenError {
+    /// The start vector is zero, there is no direction to iterate.
+    ZeroVector,
+    /// No eigenvalue is wanted, `k` is zero.
+    NothingWanted,
+    /// A block of more start vectors than the dimension.
+    TooManyVectors {
+        vectors: usize,
+        dimension: usize,
+    },
+    /// The start vectors, or their image under the matrix, are linearly dependent.
+    RankDeficient,
+    Lapack(LinalgError),
+}
+
+impl Display for EigenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EigenError::ZeroVector => write!(f, "start vector is zero"),
+            EigenError::NothingWanted => write!(f, "at least one eigenvalue must be wanted"),
+            EigenError::TooManyVectors { vectors, dimension } => {
+                write!(f, "{vectors} start vectors in dimension {dimension}")
+            }
+            EigenError::RankDeficient => write!(f, "start vectors are linearly dependent"),
+            EigenError::Lapack(source) => write!(f, "LAPACK failure: {source}"),
+        }
+    }
+}
+
+impl Error for EigenError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EigenError::Lapack(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<LinalgError> for EigenError {
+    fn from(error: LinalgError) -> Self {
+        EigenError::Lapack(error)
+    }
+}
+
+/// `vector / ||vector||`, rejecting a zero vector.
+pub(crate) fn normalized(vector: ArrayView1<f64>) -> Result<Array1<f64>, EigenError> {
+    let norm = vector.norm();
+    if norm == 0.0 {
+        return Err(EigenError::ZeroVector);
+    }
+    Ok(&vector / norm)
+}
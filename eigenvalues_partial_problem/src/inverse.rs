@@ -0,0 +1,187 @@
+use ndarray::{Array2, ArrayView1, ArrayView2};
+use ndarray_linalg::{Factorize, Norm, Solve};
+
+use matrices::Observer;
+
+use crate::error::{normalized, EigenError};
+use crate::{converged, posterior_error, Convergence, EigenvalueResult, Iteration, MAX_STEPS};
+
+/// `A - σI`.
+pub(crate) fn shifted(matrix: ArrayView2<f64>, shift: f64) -> Array2<f64> {
+    &matrix - &(Array2::<f64>::eye(matrix.nrows()) * shift)
+}
+
+/// Unit `x`, `λ = xᵀ A x` and `||A x - λ x||`.
+fn rayleigh_quotient(matrix: ArrayView2<f64>, x: ArrayView1<f64>) -> (f64, f64) {
+    let product = matrix.dot(&x);
+    let eigenvalue = x.dot(&product);
+    (eigenvalue, posterior_error(x, product.view(), eigenvalue))
+}
+
+/// Inverse iteration `(A - σI) y_{k+1} = x_k` for the eigenvalue closest to `shift`,
+/// `A - σI` is factorized once.
+/// A shift that is exactly an eigenvalue is moved by `ε ||A||`,
+/// an error is returned when `A - σI` is still singular or `vector` is zero.
+/// Stops when the residual is below `epsilon` or after [`MAX_STEPS`] steps.
+pub fn inverse_iteration(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    shift: f64,
+    epsilon: f64,
+) -> Result<EigenvalueResult, EigenError> {
+    inverse_iteration_observed(matrix, vector, shift, epsilon, &mut ())
+}
+
+/// [`inverse_iteration`] reporting every step to `observer`.
+pub fn inverse_iteration_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    shift: f64,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let mut x = normalized(vector)?;
+    let lu = match shifted(matrix, shift).factorize() {
+        Ok(lu) => lu,
+        Err(_) => {
+            let shift = shift + f64::EPSILON * matrix.norm_max().max(shift.abs());
+            shifted(matrix, shift).factorize()?
+        }
+    };
+
+    let (mut eigenvalue, mut error) = rayleigh_quotient(matrix, x.view());
+    let mut steps = 0;
+    while error > epsilon && steps < MAX_STEPS {
+        let y = lu.solve(&x)?;
+        x = &y / y.norm();
+        (eigenvalue, error) = rayleigh_quotient(matrix, x.view());
+        steps += 1;
+        observer.observe(Iteration {
+            step: steps,
+            eigenvalue,
+            error,
+        });
+    }
+
+    Ok(EigenvalueResult {
+        eigenvalue,
+        eigenvector: x,
+        error,
+        steps,
+        convergence: converged(error, epsilon),
+    })
+}
+
+/// Inverse iteration with the shift `μ_k = x_kᵀ A x_k` updated every step,
+/// converges cubically for symmetric matrices.
+///
+/// A singular `A - μ_k I` ends the iteration, as converged when the residual is
+/// at rounding level `n ε ||A||_max` and as [`Convergence::Singular`] otherwise.
+/// Otherwise stops when the residual is below `epsilon` or after [`MAX_STEPS`] steps.
+/// A zero `vector` is an error.
+pub fn rayleigh_quotient_iteration(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+) -> Result<EigenvalueResult, EigenError> {
+    rayleigh_quotient_iteration_observed(matrix, vector, epsilon, &mut ())
+}
+
+/// [`rayleigh_quotient_iteration`] reporting every step to `observer`.
+pub fn rayleigh_quotient_iteration_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let mut x = normalized(vector)?;
+    let (mut eigenvalue, mut error) = rayleigh_quotient(matrix, x.view());
+    let mut steps = 0;
+    let mut convergence = None;
+    while error > epsilon && steps < MAX_STEPS {
+        // a singular A - μI means μ is an eigenvalue to working precision, not that x is its vector
+        let Ok(y) = shifted(matrix, eigenvalue).solve(&x) else {
+            let rounding = matrix.nrows() as f64 * f64::EPSILON * matrix.norm_max();
+            convergence = Some(if error <= rounding {
+                Convergence::Converged
+            } else {
+                Convergence::Singular
+            });
+            break;
+        };
+        x = &y / y.norm();
+        (eigenvalue, error) = rayleigh_quotient(matrix, x.view());
+        steps += 1;
+        observer.observe(Iteration {
+            step: steps,
+            eigenvalue,
+            error,
+        });
+    }
+
+    Ok(EigenvalueResult {
+        eigenvalue,
+        eigenvector: x,
+        error,
+        steps,
+        convergence: convergence.unwrap_or(converged(error, epsilon)),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+
+    use ndarray::{array, Array1, Array2};
+
+    use matrices::Examples;
+
+    use crate::{inverse_iteration, rayleigh_quotient_iteration, Convergence, EigenError};
+
+    #[test]
+    fn inverse_iteration_finds_eigenvalue_closest_to_shift() {
+        let n = 21;
+        let matrix = Examples::second_difference(n);
+        let vector = Examples::random_vector(n);
+        let exact = |k: usize| 2.0 - 2.0 * (k as f64 * PI / (n + 1) as f64).cos();
+
+        for k in [1, 7, 21] {
+            let result =
+                inverse_iteration(matrix.view(), vector.view(), exact(k) + 1e-3, 1e-12).unwrap();
+            assert!((result.eigenvalue - exact(k)).abs() < 1e-12);
+            assert!(result.error <= 1e-12);
+        }
+
+        // λ_11 = 2 exactly, A - 2I is singular
+        let exact_shift = inverse_iteration(matrix.view(), vector.view(), 2.0, 1e-12).unwrap();
+        assert!((exact_shift.eigenvalue - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn rayleigh_quotient_iteration_converges_in_few_steps() {
+        let matrix = Examples::hilbert(9);
+        let vector = Array1::ones(9);
+        let result = rayleigh_quotient_iteration(matrix.view(), vector.view(), 1e-12).unwrap();
+
+        assert!(result.error <= 1e-12);
+        // ones are close to the Perron vector, λ_max = 1.7258826609...
+        assert!(result.steps <= 4);
+        assert!((result.eigenvalue - 1.725_882_660_901_846).abs() < 1e-12);
+        let residual = matrix.dot(&result.eigenvector) - result.eigenvalue * &result.eigenvector;
+        assert!(residual.iter().all(|r| r.abs() < 1e-12));
+
+        // μ_0 = 2 is exactly an eigenvalue, but x_0 = (1, 1, 1, 1) / 2 mixes all eigenvectors
+        let diagonal = Array2::from_diag(&array![1.0, 2.0, 2.0, 3.0]);
+        let mixed = Array1::ones(4);
+        let result = rayleigh_quotient_iteration(diagonal.view(), mixed.view(), 1e-12).unwrap();
+        assert_eq!(result.convergence, Convergence::Singular);
+        assert_eq!(result.steps, 0);
+        assert!((result.error - FRAC_1_SQRT_2).abs() < 1e-15);
+
+        let zero = Array1::zeros(4);
+        let error = rayleigh_quotient_iteration(diagonal.view(), zero.view(), 1e-12);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+        let error = inverse_iteration(diagonal.view(), zero.view(), 0.5, 1e-12);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+    }
+}
@@ -0,0 +1,344 @@
+use ndarray::{Array1, Array2, ArrayView1, ArrayView2};
+use ndarray_linalg::Norm;
+
+use eigenvalues_problem::cyclic_jacobi_method;
+use matrices::Observer;
+
+use crate::error::{normalized, EigenError};
+use crate::EigenpairsIteration;
+
+/// Jacobi tolerance for the tridiagonal matrix relative to its norm.
+const TRIDIAGONAL_TOLERANCE: f64 = 1e-14;
+/// Steps between the eigensolves of `T_m` that check convergence.
+const CHECK_INTERVAL: usize = 5;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Extreme {
+    Largest,
+    Smallest,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Reorthogonalization {
+    /// Against every Lanczos vector, twice.
+    Full,
+    /// Against the Ritz vectors with `β_m |s_mi| <= √ε ||T_m||` (Parlett and Scott),
+    /// the only directions in which orthogonality is lost.
+    Selective,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct LanczosResult {
+    /// The wanted Ritz values, the most extreme first.
+    pub eigenvalues: Vec<f64>,
+    /// Column `i` belongs to `eigenvalues[i]`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::matrix"))]
+    pub eigenvectors: Array2<f64>,
+    /// `||A y - θ y|| = β_m |s_mi|`.
+    pub residuals: Vec<f64>,
+    /// The step and the wanted Ritz values at every convergence check.
+    pub history: Vec<(u32, Vec<f64>)>,
+    pub steps: u32,
+}
+
+/// Ritz values and vectors of `T_m`, in ascending order.
+struct TridiagonalPairs {
+    values: Vec<f64>,
+    vectors: Array2<f64>,
+}
+
+impl TridiagonalPairs {
+    /// `T_m` with `alphas` on the diagonal and `betas` next to it, solved by the cyclic Jacobi method.
+    fn new(alphas: &[f64], betas: &[f64]) -> Self {
+        let m = alphas.len();
+        let tridiagonal = Array2::from_shape_fn((m, m), |(i, j)| {
+            if i == j {
+                alphas[i]
+            } else if i.abs_diff(j) == 1 {
+                betas[i.min(j)]
+            } else {
+                0.0
+            }
+        });
+        let norm = tridiagonal_norm(alphas, betas);
+        let result = cyclic_jacobi_method(tridiagonal.view(), TRIDIAGONAL_TOLERANCE * norm);
+
+        let mut order = (0..m).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| result.eigenvalues()[a].total_cmp(&result.eigenvalues()[b]));
+        let vectors = Array2::from_shape_fn((m, m), |(i, j)| result.eigenvectors()[(i, order[j])]);
+        Self {
+            values: order.iter().map(|&i| result.eigenvalues()[i]).collect(),
+            vectors,
+        }
+    }
+
+    /// Indices of the `k` most extreme values, the most extreme first.
+    fn extreme(&self, k: usize, extreme: Extreme) -> Vec<usize> {
+        let m = self.values.len();
+        let k = k.min(m);
+        match extreme {
+            Extreme::Largest => (m - k..m).rev().collect(),
+            Extreme::Smallest => (0..k).collect(),
+        }
+    }
+
+    fn residual(&self, beta: f64, i: usize) -> f64 {
+        beta * self.vectors[(self.values.len() - 1, i)].abs()
+    }
+
+    /// `y = Q_m s_i`.
+    fn ritz_vector(&self, basis: &[Array1<f64>], i: usize) -> Array1<f64> {
+        basis
+            .iter()
+            .zip(self.vectors.column(i))
+            .fold(Array1::zeros(basis[0].len()), |y, (q, s)| y + *s * q)
+    }
+}
+
+/// `||T_m||_∞` bound.
+fn tridiagonal_norm(alphas: &[f64], betas: &[f64]) -> f64 {
+    let alpha = alphas.iter().fold(0.0, |max, a| f64::max(max, a.abs()));
+    let beta = betas.iter().fold(0.0, |max, b| f64::max(max, b.abs()));
+    alpha + 2.0 * beta
+}
+
+/// Twice is enough (Kahan and Parlett).
+pub(crate) fn orthogonalize(w: &mut Array1<f64>, directions: &[Array1<f64>]) {
+    for _ in 0..2 {
+        for direction in directions {
+            let projection = direction.dot(w);
+            w.scaled_add(-projection, direction);
+        }
+    }
+}
+
+/// Ritz values in ascending order after `m` steps with full reorthogonalization,
+/// fewer on an invariant subspace.
+pub(crate) fn ritz_values(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, m: usize) -> Vec<f64> {
+    let m = m.min(matrix.nrows());
+    let mut basis = vec![&vector / vector.norm()];
+    let mut alphas = Vec::new();
+    let mut betas = Vec::new();
+    loop {
+        let q = &basis[basis.len() - 1];
+        let mut w = matrix.dot(q);
+        alphas.push(q.dot(&w));
+        // against the whole basis, which includes the three-term recurrence
+        orthogonalize(&mut w, &basis);
+        let beta = w.norm();
+
+        let norm = tridiagonal_norm(&alphas, &betas).max(beta);
+        if alphas.len() == m || beta <= f64::EPSILON * norm {
+            return TridiagonalPairs::new(&alphas, &betas).values;
+        }
+        betas.push(beta);
+        basis.push(w / beta);
+    }
+}
+
+/// `k` extreme eigenpairs of a symmetric matrix by the Lanczos process
+/// `A Q_m = Q_m T_m + β_m q_{m+1} e_mᵀ`, started from `vector`.
+///
+/// `T_m` is solved every `CHECK_INTERVAL` steps, which stops the iteration when
+/// the residuals of all wanted Ritz pairs are below `epsilon`, and on an invariant subspace
+/// (`β_m ≈ 0`) or after `n` steps.
+/// Selective reorthogonalization uses the Ritz vectors of the last check.
+pub fn lanczos(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    k: usize,
+    extreme: Extreme,
+    reorthogonalization: Reorthogonalization,
+    epsilon: f64,
+) -> Result<LanczosResult, EigenError> {
+    lanczos_observed(
+        matrix,
+        vector,
+        k,
+        extreme,
+        reorthogonalization,
+        epsilon,
+        &mut (),
+    )
+}
+
+/// [`lanczos`] reporting the wanted Ritz pairs at every convergence check to `observer`.
+pub fn lanczos_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    k: usize,
+    extreme: Extreme,
+    reorthogonalization: Reorthogonalization,
+    epsilon: f64,
+    observer: &mut impl Observer<EigenpairsIteration>,
+) -> Result<LanczosResult, EigenError> {
+    let n = matrix.nrows();
+
+    let mut basis = vec![normalized(vector)?];
+    let mut alphas = Vec::new();
+    let mut betas: Vec<f64> = Vec::new();
+    let mut converged: Vec<Array1<f64>> = Vec::new();
+    let mut history = Vec::new();
+    loop {
+        let m = basis.len();
+        let q = &basis[m - 1];
+        let mut w = matrix.dot(q);
+        if let Some(&beta) = betas.last() {
+            w.scaled_add(-beta, &basis[m - 2]);
+        }
+        let alpha = q.dot(&w);
+        w.scaled_add(-alpha, q);
+        alphas.push(alpha);
+
+        let directions = match reorthogonalization {
+            Reorthogonalization::Full => &basis,
+            Reorthogonalization::Selective => &converged,
+        };
+        orthogonalize(&mut w, directions);
+        let mut beta = w.norm();
+        if reorthogonalization == Reorthogonalization::Selective
+            && beta <= f64::EPSILON.sqrt() * tridiagonal_norm(&alphas, &betas)
+        {
+            // a single step this small already loses semi-orthogonality
+            orthogonalize(&mut w, &basis);
+            beta = w.norm();
+        }
+
+        let norm = tridiagonal_norm(&alphas, &betas).max(beta);
+        let invariant = beta <= f64::EPSILON * norm;
+        if m % CHECK_INTERVAL == 0 || invariant || m == n {
+            let pairs = TridiagonalPairs::new(&alphas, &betas);
+            let wanted = pairs.extreme(k, extreme);
+            let eigenvalues = wanted.iter().map(|&i| pairs.values[i]).collect::<Vec<_>>();
+            let residuals = wanted
+                .iter()
+                .map(|&i| pairs.residual(beta, i))
+                .collect::<Vec<_>>();
+            history.push((m as u32, eigenvalues.clone()));
+            observer.observe(EigenpairsIteration {
+                step: m as u32,
+                eigenvalues: eigenvalues.clone(),
+                errors: residuals.clone(),
+            });
+
+            let done = wanted.len() == k.min(n) && residuals.iter().all(|&r| r < epsilon);
+            if done || invariant || m == n {
+                let mut eigenvectors = Array2::zeros((n, wanted.len()));
+                for (mut column, &i) in eigenvectors.columns_mut().into_iter().zip(&wanted) {
+                    column.assign(&pairs.ritz_vector(&basis, i));
+                }
+                return Ok(LanczosResult {
+                    eigenvalues,
+                    eigenvectors,
+                    residuals,
+                    history,
+                    steps: m as u32,
+                });
+            }
+
+            if reorthogonalization == Reorthogonalization::Selective {
+                converged = (0..m)
+                    .filter(|&i| pairs.residual(beta, i) <= f64::EPSILON.sqrt() * norm)
+                    .map(|i| pairs.ritz_vector(&basis, i))
+                    .collect();
+            }
+        }
+        betas.push(beta);
+        basis.push(w / beta);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1, Array2, Axis};
+
+    use matrices::Examples;
+
+    use crate::{lanczos, EigenError, Extreme, Reorthogonalization};
+
+    /// `H D H` for a Householder reflection `H`, spectrum in `[0, 1]` and outliers `2, 3, 4, -1, -2, -3`.
+    fn outliers(n: usize) -> Array2<f64> {
+        let mut spectrum = (0..n - 6)
+            .map(|i| i as f64 / (n - 7) as f64)
+            .collect::<Vec<_>>();
+        spectrum.extend([2.0, 3.0, 4.0, -1.0, -2.0, -3.0]);
+
+        let u = Examples::random_vector(n);
+        let column = u.view().insert_axis(Axis(1));
+        let reflection = Array2::eye(n) - 2.0 * column.dot(&column.t()) / u.dot(&u);
+        reflection
+            .dot(&Array2::from_diag(&Array1::from(spectrum)))
+            .dot(&reflection)
+    }
+
+    #[test]
+    fn extreme_eigenpairs_with_both_reorthogonalizations() {
+        let n = 200;
+        let matrix = outliers(n);
+        let vector = Examples::random_vector(n);
+
+        for reorthogonalization in [Reorthogonalization::Full, Reorthogonalization::Selective] {
+            for (extreme, expected) in [
+                (Extreme::Largest, [4.0, 3.0, 2.0]),
+                (Extreme::Smallest, [-3.0, -2.0, -1.0]),
+            ] {
+                let result = lanczos(
+                    matrix.view(),
+                    vector.view(),
+                    3,
+                    extreme,
+                    reorthogonalization,
+                    1e-10,
+                )
+                .unwrap();
+
+                assert!(result.steps < 50);
+                assert_eq!(result.history.last().unwrap().0, result.steps);
+                assert!(result.residuals.iter().all(|&r| r < 1e-10));
+                for (lambda, expected) in result.eigenvalues.iter().zip(expected) {
+                    assert!((lambda - expected).abs() < 1e-12);
+                }
+
+                let vectors = &result.eigenvectors;
+                let product = matrix.dot(vectors);
+                for (i, lambda) in result.eigenvalues.iter().enumerate() {
+                    let residual = &product.column(i) - &(&vectors.column(i) * *lambda);
+                    assert!(residual.iter().all(|r| r.abs() < 1e-9));
+                }
+                assert!(vectors.t().dot(vectors).abs_diff_eq(&Array2::eye(3), 1e-10));
+            }
+        }
+    }
+
+    #[test]
+    fn invariant_subspace_stops_iteration() {
+        let matrix = Array2::from_diag(&array![1.0, 2.0, 3.0, 4.0, 5.0]);
+        let vector = array![1.0, 1.0, 0.0, 0.0, 0.0];
+        let result = lanczos(
+            matrix.view(),
+            vector.view(),
+            1,
+            Extreme::Largest,
+            Reorthogonalization::Full,
+            1e-12,
+        )
+        .unwrap();
+
+        assert_eq!(result.steps, 2);
+        assert!((result.eigenvalues[0] - 2.0).abs() < 1e-14);
+
+        let zero = Array1::zeros(5);
+        let error = lanczos(
+            matrix.view(),
+            zero.view(),
+            1,
+            Extreme::Largest,
+            Reorthogonalization::Full,
+            1e-12,
+        );
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+    }
+}
@@ -1,82 +1,614 @@
 use ndarray::{Array1, ArrayView1, ArrayView2};
 use ndarray_linalg::Norm;
 
+use matrices::Observer;
+
+pub use acceleration::*;
+pub use arnoldi::*;
+pub use deflation::*;
+pub use error::*;
+pub use inverse::*;
+pub use lanczos::*;
+
+mod acceleration;
+mod arnoldi;
+mod deflation;
+mod error;
+mod inverse;
+mod lanczos;
+
+/// Step limit of every iteration of this crate, reached it ends with [`Convergence::MaxSteps`].
+pub const MAX_STEPS: u32 = 10_000;
+/// `|λ_1 + λ_2| < |λ_1| / OSCILLATION_RATIO` counts as a `±λ` pair.
+const OSCILLATION_RATIO: f64 = 1e3;
+
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct EigenvalueResult {
     pub eigenvalue: f64,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     pub eigenvector: Array1<f64>,
     pub error: f64,
     pub steps: u32,
+    pub convergence: Convergence,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub enum Convergence {
+    Converged,
+    /// [`MAX_STEPS`] iterations without reaching the tolerance.
+    MaxSteps,
+    /// The dominant eigenvalues are a pair `±λ`, the iterates alternate between two directions.
+    /// The result is the pair member with the larger component in the last iterate.
+    Oscillating,
+    /// `A - μI` became exactly singular in [`rayleigh_quotient_iteration`]
+    /// while the residual of the iterate was still above rounding level.
+    Singular,
+}
+
+#[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct DotProductResult {
+    /// The eigenvalue and the unit right eigenvector `A x = λ x`.
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub right: EigenvalueResult,
+    /// Unit `y` with `yᵀ A = λ yᵀ`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
+    pub left_eigenvector: Array1<f64>,
+}
+
+impl DotProductResult {
+    /// `1 / |yᵀ x|` for the unit left and right eigenvectors, a perturbation `E` of `A`
+    /// moves the eigenvalue by about `||E|| / |yᵀ x|`. One for symmetric matrices.
+    ///
+    /// `None` when `yᵀ x = 0`, as for a defective eigenvalue or when the vectors
+    /// have not converged to the same eigenvalue.
+    pub fn condition_number(&self) -> Option<f64> {
+        let product = self.left_eigenvector.dot(&self.right.eigenvector);
+        (product != 0.0).then(|| 1.0 / product.abs())
+    }
+}
+
+/// The right eigenvector alone, for callers of the plain power iteration.
+impl From<DotProductResult> for EigenvalueResult {
+    fn from(result: DotProductResult) -> Self {
+        result.right
+    }
+}
+
+/// Estimates after a step of an iteration.
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct Iteration {
+    pub step: u32,
+    pub eigenvalue: f64,
+    /// The a-posteriori error of the method.
+    pub error: f64,
+}
+
+/// [`Iteration`] of the power iteration for the pair `pair` of a [`deflation`].
+#[derive(Debug, Copy, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct PairIteration {
+    pub pair: usize,
+    pub step: u32,
+    pub eigenvalue: f64,
+    pub error: f64,
+}
+
+/// Ritz values and their residuals after a step of a method for several eigenpairs,
+/// complex for [`arnoldi`].
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct EigenpairsIteration<T = f64> {
+    pub step: u32,
+    pub eigenvalues: Vec<T>,
+    pub errors: Vec<f64>,
 }
 
+/// The CSV table holds the eigenvector, the scalars are in the JSON document.
+#[cfg(feature = "serde")]
+impl matrices::export::Export for EigenvalueResult {
+    type Record = matrices::export::ComponentRecord;
+
+    fn records(&self) -> Vec<Self::Record> {
+        matrices::export::components(&self.eigenvector)
+    }
+}
+
+/// Row `index,right,left` of the CSV table of a [`DotProductResult`].
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize)]
+pub struct EigenvectorsRecord {
+    pub index: usize,
+    pub right: f64,
+    pub left: f64,
+}
+
+/// The CSV table holds both eigenvectors, the scalars are in the JSON document.
+#[cfg(feature = "serde")]
+impl matrices::export::Export for DotProductResult {
+    type Record = EigenvectorsRecord;
+
+    fn records(&self) -> Vec<Self::Record> {
+        self.right
+            .eigenvector
+            .iter()
+            .zip(&self.left_eigenvector)
+            .enumerate()
+            .map(|(index, (&right, &left))| EigenvectorsRecord { index, right, left })
+            .collect()
+    }
+}
+
+/// Power iteration `x_{k+1} = A x_k / ||A x_k||` with the Rayleigh quotient `λ_k = x_kᵀ A x_k`,
+/// so the iterates neither overflow nor underflow and the sign of `λ` is kept.
+///
+/// Stops when `||A x_k - λ_k x_k|| <= epsilon`, after [`MAX_STEPS`] steps,
+/// or when `A² x_k ≈ μ x_k` holds while `A x_k ≈ λ x_k` does not, that is on a dominant pair `±√μ`.
+/// A zero `vector` is an error.
 pub fn power_iteration(
     matrix: ArrayView2<f64>,
     vector: ArrayView1<f64>,
     epsilon: f64,
-) -> EigenvalueResult {
-    let mut prev = vector.to_owned();
-    let mut next = matrix.dot(&prev);
-    let mut eigenvalue = (next.dot(&next) / prev.dot(&prev)).sqrt();
+) -> Result<EigenvalueResult, EigenError> {
+    power_iteration_observed(matrix, vector, epsilon, &mut ())
+}
 
-    let mut error = posterior_error(prev.view(), next.view(), eigenvalue);
-    let mut steps = 0;
-    while error > epsilon {
-        let buf = matrix.dot(&next);
+/// [`power_iteration`] reporting every step to `observer`.
+pub fn power_iteration_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<EigenvalueResult, EigenError> {
+    let mut x = normalized(vector)?;
+    let mut product = matrix.dot(&x);
+    let mut eigenvalue = x.dot(&product);
 
-        prev = next;
-        next = buf;
-        eigenvalue = (next.dot(&next) / prev.dot(&prev)).sqrt();
+    let mut error = posterior_error(x.view(), product.view(), eigenvalue);
+    let mut steps = 0;
+    let convergence = loop {
+        if error <= epsilon {
+            break Convergence::Converged;
+        }
+        if steps == MAX_STEPS {
+            break Convergence::MaxSteps;
+        }
 
-        error = posterior_error(prev.view(), next.view(), eigenvalue);
+        let next = &product / product.norm();
+        let next_product = matrix.dot(&next);
         steps += 1;
-    }
 
-    EigenvalueResult {
+        if let Some(pair) = dominant_pair(
+            x.view(),
+            product.view(),
+            next_product.view(),
+            epsilon,
+            error,
+        ) {
+            (eigenvalue, x, error) = pair;
+            observer.observe(Iteration {
+                step: steps,
+                eigenvalue,
+                error,
+            });
+            break Convergence::Oscillating;
+        }
+
+        x = next;
+        product = next_product;
+        eigenvalue = x.dot(&product);
+        error = posterior_error(x.view(), product.view(), eigenvalue);
+        observer.observe(Iteration {
+            step: steps,
+            eigenvalue,
+            error,
+        });
+    };
+
+    Ok(EigenvalueResult {
         eigenvalue,
-        eigenvector: next,
+        eigenvector: x,
         error,
         steps,
+        convergence,
+    })
+}
+
+/// Eigenpair `(±√μ, x ± A x / √μ)` of the pair `±√μ` with `μ = xᵀ A² x` for the unit `x`,
+/// taking the sign with the larger vector, and its error.
+/// `A² x = ||A x|| next_product` is never formed, its norm would overflow long before `A x` does.
+///
+/// `None` unless `||A² x - μ x|| / √μ <= epsilon` while the `error` of `A x ≈ λ x` is
+/// `OSCILLATION_RATIO` times larger: for `x = u_1 + δ u_2` the ratio is `|λ_1| / |λ_1 + λ_2|`.
+fn dominant_pair(
+    x: ArrayView1<f64>,
+    product: ArrayView1<f64>,
+    next_product: ArrayView1<f64>,
+    epsilon: f64,
+    error: f64,
+) -> Option<(f64, Array1<f64>, f64)> {
+    let norm = product.norm();
+    let quotient = x.dot(&next_product);
+    let mu = norm * quotient;
+    if mu <= 0.0 {
+        return None;
+    }
+    let root = mu.sqrt();
+    // A² x / √μ
+    let squared = &next_product * (norm / root);
+    let pair_error = (&next_product - quotient * &x).norm() * (norm / root);
+    if pair_error > epsilon || error <= OSCILLATION_RATIO * pair_error {
+        return None;
     }
+
+    let (plus, minus) = (&x + &(&product / root), &x - &(&product / root));
+    let (eigenvalue, vector, image) = if plus.norm() >= minus.norm() {
+        (root, plus, &product + &squared)
+    } else {
+        (-root, minus, &product - &squared)
+    };
+    let length = vector.norm();
+    let error = posterior_error(vector.view(), image.view(), eigenvalue);
+    Some((eigenvalue, vector / length, error))
 }
 
+/// Power iterations with `A` and `Aᵀ` from the same `vector` and the scalar product estimate
+/// `λ_k = y_kᵀ A x_k / y_kᵀ x_k`.
+///
+/// Both sequences are normalized, the error is the larger of the right and the left residuals.
+/// The estimate itself is accurate to `(λ_2 / λ_1)^{2k}` for a diagonalizable matrix, but the
+/// residuals, and so the number of steps, decrease only as `(λ_2 / λ_1)^k`.
+/// Stops when the error is below `epsilon` or after [`MAX_STEPS`] steps.
+/// A zero `vector` is an error.
 pub fn dot_product_method(
     matrix: ArrayView2<f64>,
     vector: ArrayView1<f64>,
     epsilon: f64,
-) -> EigenvalueResult {
+) -> Result<DotProductResult, EigenError> {
+    dot_product_method_observed(matrix, vector, epsilon, &mut ())
+}
+
+/// [`dot_product_method`] reporting every step to `observer`.
+pub fn dot_product_method_observed(
+    matrix: ArrayView2<f64>,
+    vector: ArrayView1<f64>,
+    epsilon: f64,
+    observer: &mut impl Observer<Iteration>,
+) -> Result<DotProductResult, EigenError> {
     let matrix_t = matrix.t();
 
-    let mut x_prev = vector.to_owned();
-    let mut x_next = matrix.dot(&x_prev);
+    let mut x = normalized(vector)?;
+    let mut x_product = matrix.dot(&x);
 
-    let y_prev = vector.to_owned();
-    let mut y_next = matrix_t.dot(&y_prev);
+    let mut y = x.clone();
+    let mut y_product = matrix_t.dot(&y);
 
-    let mut eigenvalue = (x_next.dot(&y_next)) / (x_prev.dot(&y_next));
-    let mut error = posterior_error(x_prev.view(), x_next.view(), eigenvalue);
+    let (mut eigenvalue, mut error) =
+        scalar_product_estimate(x.view(), x_product.view(), y.view(), y_product.view());
     let mut steps = 0;
 
-    while error > epsilon {
-        let x_buf = matrix.dot(&x_next);
-        x_prev = x_next;
-        x_next = x_buf;
+    while error > epsilon && steps < MAX_STEPS {
+        x = &x_product / x_product.norm();
+        x_product = matrix.dot(&x);
 
-        let y_buf = matrix_t.dot(&y_next);
-        y_next = y_buf;
+        y = &y_product / y_product.norm();
+        y_product = matrix_t.dot(&y);
 
-        eigenvalue = (x_next.dot(&y_next)) / (x_prev.dot(&y_next));
-        error = posterior_error(x_prev.view(), x_next.view(), eigenvalue);
+        (eigenvalue, error) =
+            scalar_product_estimate(x.view(), x_product.view(), y.view(), y_product.view());
         steps += 1;
+        observer.observe(Iteration {
+            step: steps,
+            eigenvalue,
+            error,
+        });
     }
 
-    EigenvalueResult {
-        eigenvalue,
-        eigenvector: x_next,
-        error,
-        steps,
+    Ok(DotProductResult {
+        right: EigenvalueResult {
+            eigenvalue,
+            eigenvector: x,
+            error,
+            steps,
+            convergence: converged(error, epsilon),
+        },
+        left_eigenvector: y,
+    })
+}
+
+/// `λ = yᵀ A x / yᵀ x` and the larger of `||A x - λ x||` and `||Aᵀ y - λ y||`.
+fn scalar_product_estimate(
+    x: ArrayView1<f64>,
+    x_product: ArrayView1<f64>,
+    y: ArrayView1<f64>,
+    y_product: ArrayView1<f64>,
+) -> (f64, f64) {
+    let eigenvalue = y.dot(&x_product) / y.dot(&x);
+    let error = f64::max(
+        posterior_error(x, x_product, eigenvalue),
+        posterior_error(y, y_product, eigenvalue),
+    );
+    (eigenvalue, error)
+}
+
+fn converged(error: f64, epsilon: f64) -> Convergence {
+    if error <= epsilon {
+        Convergence::Converged
+    } else {
+        Convergence::MaxSteps
     }
 }
 
 fn posterior_error(prev: ArrayView1<f64>, next: ArrayView1<f64>, eigenvalue: f64) -> f64 {
     (&next - eigenvalue * &prev).norm() / prev.norm()
 }
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, s, Array1, ArrayView1};
+    use ndarray_linalg::{c64, Norm};
+
+    use matrices::Examples;
+
+    use crate::{
+        accelerated_power_iteration_observed, arnoldi_observed, deflation_observed,
+        dot_product_method, lanczos_observed, power_iteration, power_iteration_observed,
+        rayleigh_quotient_iteration_observed, subspace_iteration_observed, Acceleration,
+        Convergence, Deflation, DotProductResult, EigenError, EigenpairsIteration,
+        EigenvalueResult, Extreme, Iteration, PairIteration, Reorthogonalization, MAX_STEPS,
+    };
+
+    #[test]
+    fn power_iteration_keeps_sign_and_scale() {
+        for scale in [1e150, 1e-150] {
+            let matrix = array![[-2.0, 1.0], [1.0, -2.0]] * scale;
+            let vector = array![1.0, 0.0];
+            let result = power_iteration(matrix.view(), vector.view(), 1e-10 * scale).unwrap();
+
+            assert_eq!(result.convergence, Convergence::Converged);
+            assert!((result.eigenvalue / scale + 3.0).abs() < 1e-10);
+            assert!((result.eigenvector.norm() - 1.0).abs() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn power_iteration_detects_plus_minus_pair() {
+        let matrix = array![[0.0, 2.0, 0.0], [2.0, 0.0, 0.0], [0.0, 0.0, 1.0]];
+        let vector = array![1.0, 0.5, 1.0];
+        let mut trace: Vec<Iteration> = Vec::new();
+        let result =
+            power_iteration_observed(matrix.view(), vector.view(), 1e-10, &mut trace).unwrap();
+
+        assert_eq!(result.convergence, Convergence::Oscillating);
+        // the observer sees the pair member that is returned
+        assert_eq!(
+            trace.last(),
+            Some(&Iteration {
+                step: result.steps,
+                eigenvalue: result.eigenvalue,
+                error: result.error,
+            })
+        );
+        assert!((result.eigenvalue.abs() - 2.0).abs() < 1e-10);
+        let residual = matrix.dot(&result.eigenvector) - result.eigenvalue * &result.eigenvector;
+        assert!(residual.norm() < 1e-9);
+        assert!((residual.norm() - result.error).abs() < 1e-15);
+    }
+
+    #[test]
+    fn power_iteration_stops_without_real_dominant_eigenvalue() {
+        let rotation = array![[0.0, -1.0], [1.0, 0.0]];
+        let vector = array![1.0, 0.0];
+        let result = power_iteration(rotation.view(), vector.view(), 1e-10).unwrap();
+
+        assert_eq!(result.convergence, Convergence::MaxSteps);
+        assert_eq!(result.steps, MAX_STEPS);
+
+        let zero = Array1::zeros(2);
+        let error = power_iteration(rotation.view(), zero.view(), 1e-10);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+    }
+
+    #[test]
+    fn dot_product_method_finds_left_and_right_eigenvectors() {
+        let matrix = array![[3.0, 1.0, 1.0], [0.0, 1.0, 1.0], [0.0, 0.0, 0.5]];
+        let vector = array![1.0, 1.0, 1.0];
+        let result = dot_product_method(matrix.view(), vector.view(), 1e-12).unwrap();
+
+        assert_eq!(result.right.convergence, Convergence::Converged);
+        assert!((result.right.eigenvalue - 3.0).abs() < 1e-12);
+        let (x, y) = (&result.right.eigenvector, &result.left_eigenvector);
+        assert!((x.norm() - 1.0).abs() < 1e-15 && (y.norm() - 1.0).abs() < 1e-15);
+        assert!((&matrix.dot(x) - 3.0 * x).norm() < 1e-12);
+        assert!((&matrix.t().dot(y) - 3.0 * y).norm() < 1e-12);
+        // y ∥ (1, 0.5, 0.6) and x = e_1
+        assert!((result.condition_number().unwrap() - 1.61_f64.sqrt()).abs() < 1e-10);
+
+        let hilbert = Examples::hilbert(9);
+        let vector = Examples::random_vector(9);
+        let result = dot_product_method(hilbert.view(), vector.view(), 1e-12).unwrap();
+        assert!((result.condition_number().unwrap() - 1.0).abs() < 1e-12);
+
+        // the Jordan block has y = e_2 and x = e_1
+        let jordan = array![[1.0, 1.0], [0.0, 1.0]];
+        let orthogonal = DotProductResult {
+            right: EigenvalueResult {
+                eigenvalue: 1.0,
+                eigenvector: array![1.0, 0.0],
+                error: 0.0,
+                steps: 0,
+                convergence: Convergence::Converged,
+            },
+            left_eigenvector: array![0.0, 1.0],
+        };
+        assert_eq!(orthogonal.condition_number(), None);
+
+        let zero = Array1::zeros(2);
+        let error = dot_product_method(jordan.view(), zero.view(), 1e-12);
+        assert!(matches!(error, Err(EigenError::ZeroVector)));
+    }
+
+    #[test]
+    fn observers_trace_every_step() {
+        let matrix = Examples::hilbert(9);
+        let vector = Examples::random_vector(9);
+
+        let mut trace: Vec<Iteration> = Vec::new();
+        let result =
+            power_iteration_observed(matrix.view(), vector.view(), 1e-12, &mut trace).unwrap();
+        assert_eq!(trace.len() as u32, result.steps);
+        let last = trace.last().unwrap();
+        assert_eq!(
+            (last.eigenvalue, last.error),
+            (result.eigenvalue, result.error)
+        );
+        assert!(trace
+            .iter()
+            .enumerate()
+            .all(|(i, it)| it.step == i as u32 + 1));
+
+        let mut errors = Vec::new();
+        let result = rayleigh_quotient_iteration_observed(
+            matrix.view(),
+            vector.view(),
+            1e-12,
+            &mut |it: Iteration| errors.push(it.error),
+        )
+        .unwrap();
+        assert_eq!(errors.len() as u32, result.steps);
+        assert_eq!(*errors.last().unwrap(), result.error);
+    }
+
+    #[test]
+    fn observers_of_several_methods_end_with_the_result() {
+        let matrix = Examples::hilbert(9);
+        let vector = Examples::random_vector(9);
+
+        for acceleration in [
+            Acceleration::Aitken,
+            Acceleration::Shift,
+            Acceleration::Chebyshev,
+        ] {
+            let mut trace: Vec<Iteration> = Vec::new();
+            let result = accelerated_power_iteration_observed(
+                matrix.view(),
+                vector.view(),
+                acceleration,
+                1e-12,
+                &mut trace,
+            )
+            .unwrap();
+            let last = trace.last().unwrap();
+            assert_eq!(
+                (last.step, last.eigenvalue, last.error),
+                (result.steps, result.eigenvalue, result.error)
+            );
+        }
+
+        let mut trace: Vec<PairIteration> = Vec::new();
+        let result = deflation_observed(
+            matrix.view(),
+            vector.view(),
+            3,
+            Deflation::Hotelling,
+            1e-10,
+            &mut trace,
+        )
+        .unwrap();
+        for pair in 0..3 {
+            let steps = trace.iter().filter(|it| it.pair == pair).count();
+            assert_eq!(steps as u32, result.steps[pair]);
+        }
+
+        let mut trace: Vec<EigenpairsIteration> = Vec::new();
+        let vectors = Examples::random_matrix(9);
+        let result = subspace_iteration_observed(
+            matrix.view(),
+            vectors.slice(s![.., ..2]),
+            1e-10,
+            &mut trace,
+        )
+        .unwrap();
+        let last = trace.last().unwrap();
+        assert_eq!(last.step, result.steps[0]);
+        assert_eq!(
+            (&last.eigenvalues, &last.errors),
+            (&result.eigenvalues, &result.errors)
+        );
+
+        let mut trace: Vec<EigenpairsIteration> = Vec::new();
+        let result = lanczos_observed(
+            matrix.view(),
+            vector.view(),
+            2,
+            Extreme::Largest,
+            Reorthogonalization::Full,
+            1e-10,
+            &mut trace,
+        )
+        .unwrap();
+        assert_eq!(trace.len(), result.history.len());
+        let last = trace.last().unwrap();
+        assert_eq!(last.step, result.steps);
+        assert_eq!(
+            (&last.eigenvalues, &last.errors),
+            (&result.eigenvalues, &result.residuals)
+        );
+
+        let mut trace: Vec<EigenpairsIteration<c64>> = Vec::new();
+        let result = arnoldi_observed(
+            |x: ArrayView1<f64>| matrix.dot(&x),
+            vector.view(),
+            2,
+            1e-10,
+            &mut trace,
+        )
+        .unwrap();
+        assert_eq!(trace.len() as u32, result.restarts + 1);
+        let last = trace.last().unwrap();
+        assert_eq!(last.step, result.steps);
+        assert_eq!(
+            (&last.eigenvalues, &last.errors),
+            (&result.eigenvalues, &result.residuals)
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn results_to_json() {
+        use matrices::export::Export;
+
+        let matrix = array![[2.0, 0.0], [0.0, 1.0]];
+        let vector = array![1.0, 0.0];
+        let result = power_iteration(matrix.view(), vector.view(), 1e-12).unwrap();
+
+        let mut json = Vec::new();
+        result.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "eigenvalue": 2.0,
+                "eigenvector": [1.0, 0.0],
+                "error": 0.0,
+                "steps": 0,
+                "convergence": "Converged"
+            })
+        );
+
+        let result = arnoldi_observed(
+            |x: ArrayView1<f64>| matrix.dot(&x),
+            vector.view(),
+            1,
+            1e-12,
+            &mut (),
+        )
+        .unwrap();
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["eigenvalues"], serde_json::json!([[2.0, 0.0]]));
+        assert_eq!(json["restarts"], 0);
+    }
+}
@@ -1,7 +1,11 @@
-use ndarray::{array, Array1, Array2};
+use ndarray::{array, s, Array1, Array2, ArrayView1};
 use ndarray_linalg::EigVals;
 
-use eigenvalues_partial_problem::{dot_product_method, power_iteration};
+use eigenvalues_partial_problem::{
+    arnoldi, compare_with_power_iteration, deflation, dot_product_method, inverse_iteration,
+    lanczos, power_iteration, rayleigh_quotient_iteration, subspace_iteration, Acceleration,
+    Deflation, EigenpairsResult, Extreme, Reorthogonalization,
+};
 use matrices::Examples;
 
 const EPSILONS: [f64; 3] = [1e-5, 1e-7, 1e-9];
@@ -22,6 +26,34 @@ fn main() {
     ];
     let vector = array![1.0, 1.0, 1.0];
     examine(matrix, vector, "-- Good matrix --");
+
+    let second_difference = Examples::second_difference(50);
+    let random_vector = Examples::random_vector(50);
+    examine_acceleration(
+        second_difference,
+        random_vector,
+        "-- Acceleration, second difference matrix --",
+    );
+
+    let hilbert_matrix = Examples::hilbert(9);
+    let random_vector = Examples::random_vector(9);
+    examine_deflation(
+        hilbert_matrix,
+        random_vector,
+        "-- Deflation, Hilbert matrix --",
+    );
+
+    let hilbert_matrix = Examples::hilbert(100);
+    let random_vector = Examples::random_vector(100);
+    examine_lanczos(
+        hilbert_matrix,
+        random_vector,
+        "-- Lanczos, Hilbert matrix --",
+    );
+
+    let random_matrix = Examples::random_matrix(60);
+    let random_vector = Examples::random_vector(60);
+    examine_arnoldi(random_matrix, random_vector, "-- Arnoldi, random matrix --");
 }
 
 fn examine(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
@@ -29,20 +61,155 @@ fn examine(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
     for epsilon in EPSILONS {
         println!("Epsilon: {epsilon:e}");
         println!("Power iteration.");
-        let lambda = power_iteration(matrix.view(), vector.view(), epsilon);
+        let lambda = power_iteration(matrix.view(), vector.view(), epsilon).unwrap();
         println!(
-            "found eigenvalue {:.6} with error {:.10}\nvector: {:?}\nsteps: {}",
-            lambda.eigenvalue, lambda.error, lambda.eigenvector, lambda.steps
+            "found eigenvalue {:.6} with error {:.10}\nvector: {:?}\nsteps: {} ({:?})",
+            lambda.eigenvalue, lambda.error, lambda.eigenvector, lambda.steps, lambda.convergence
         );
         println!("Actual eigenvalues: {:.2}", matrix.eigvals().unwrap());
 
         println!("Dot product.");
-        let lambda_dot = dot_product_method(matrix.view(), vector.view(), epsilon);
+        let lambda_dot = dot_product_method(matrix.view(), vector.view(), epsilon).unwrap();
         println!(
             "found eigenvalue {:.6} with error {:.10}\nvector: {:?}\nsteps: {}",
-            lambda_dot.eigenvalue, lambda_dot.error, lambda_dot.eigenvector, lambda_dot.steps
+            lambda_dot.right.eigenvalue,
+            lambda_dot.right.error,
+            lambda_dot.right.eigenvector,
+            lambda_dot.right.steps
+        );
+        println!(
+            "left vector: {:?}\ncondition number: {:.6?}",
+            lambda_dot.left_eigenvector,
+            lambda_dot.condition_number()
+        );
+
+        // ||A||_∞ bounds the spectrum, ±||A||_∞ with the sign of the dominant eigenvalue
+        // is closer to it than to any other eigenvalue
+        let norm: f64 = matrix
+            .rows()
+            .into_iter()
+            .map(|row| row.map(|x| x.abs()).sum())
+            .fold(0.0, f64::max);
+        let shift = norm.copysign(lambda.eigenvalue);
+        println!("Inverse iteration, shift {shift:.6}.");
+        let lambda_inverse =
+            inverse_iteration(matrix.view(), vector.view(), shift, epsilon).unwrap();
+        println!(
+            "found eigenvalue {:.6} with error {:.10}\nvector: {:?}\nsteps: {}",
+            lambda_inverse.eigenvalue,
+            lambda_inverse.error,
+            lambda_inverse.eigenvector,
+            lambda_inverse.steps
+        );
+
+        println!("Rayleigh quotient iteration.");
+        let lambda_rayleigh =
+            rayleigh_quotient_iteration(matrix.view(), vector.view(), epsilon).unwrap();
+        println!(
+            "found eigenvalue {:.6} with error {:.10}\nvector: {:?}\nsteps: {}",
+            lambda_rayleigh.eigenvalue,
+            lambda_rayleigh.error,
+            lambda_rayleigh.eigenvector,
+            lambda_rayleigh.steps
         );
         println!("--------------------------------")
     }
     println!()
 }
+
+fn examine_acceleration(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
+    println!("{message}");
+    for acceleration in [
+        Acceleration::Aitken,
+        Acceleration::Shift,
+        Acceleration::Chebyshev,
+    ] {
+        println!("{acceleration:?}.");
+        let comparison =
+            compare_with_power_iteration(matrix.view(), vector.view(), acceleration, 1e-9).unwrap();
+        let lambda = &comparison.accelerated;
+        println!(
+            "found eigenvalue {:.10} with error {:.2e}\nsteps: {} ({:?}), saved: {}",
+            lambda.eigenvalue,
+            lambda.error,
+            lambda.steps,
+            lambda.convergence,
+            comparison.saved_steps()
+        );
+    }
+    println!()
+}
+
+fn examine_deflation(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
+    println!("{message}");
+    let print = |result: EigenpairsResult| {
+        println!(
+            "found eigenvalues {:.10} with errors {:.2e}\nsteps: {:?} ({:?})",
+            Array1::from(result.eigenvalues),
+            Array1::from(result.errors),
+            result.steps,
+            result.convergence
+        );
+    };
+    for method in [Deflation::Hotelling, Deflation::Wielandt] {
+        println!("{method:?} deflation.");
+        print(deflation(matrix.view(), vector.view(), 3, method, 1e-9).unwrap());
+    }
+    println!("Subspace iteration.");
+    let vectors = Examples::random_matrix(matrix.nrows());
+    print(subspace_iteration(matrix.view(), vectors.slice(s![.., ..3]), 1e-9).unwrap());
+    println!()
+}
+
+fn examine_lanczos(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
+    println!("{message}");
+    for extreme in [Extreme::Largest, Extreme::Smallest] {
+        for reorthogonalization in [Reorthogonalization::Full, Reorthogonalization::Selective] {
+            println!("{extreme:?}, {reorthogonalization:?} reorthogonalization.");
+            let result = lanczos(
+                matrix.view(),
+                vector.view(),
+                3,
+                extreme,
+                reorthogonalization,
+                1e-9,
+            )
+            .unwrap();
+            println!(
+                "found eigenvalues {:.6e} with residuals {:.2e}\nsteps: {}",
+                Array1::from(result.eigenvalues),
+                Array1::from(result.residuals),
+                result.steps
+            );
+        }
+    }
+    println!()
+}
+
+fn examine_arnoldi(matrix: Array2<f64>, vector: Array1<f64>, message: &str) {
+    println!("{message}");
+    for epsilon in EPSILONS {
+        println!("Epsilon: {epsilon:e}");
+        let result = arnoldi(
+            |x: ArrayView1<f64>| matrix.dot(&x),
+            vector.view(),
+            4,
+            epsilon,
+        )
+        .unwrap();
+        println!(
+            "found eigenvalues {:.6}\nresiduals: {:.2e}\nrestarts: {}, steps: {}",
+            Array1::from(result.eigenvalues),
+            Array1::from(result.residuals),
+            result.restarts,
+            result.steps
+        );
+    }
+    let mut actual = matrix.eigvals().unwrap().to_vec();
+    actual.sort_by(|a, b| b.norm().total_cmp(&a.norm()));
+    println!(
+        "Actual eigenvalues: {:.6}",
+        Array1::from(actual[..4].to_vec())
+    );
+    println!()
+}
@@ -0,0 +1,44 @@
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use ndarray::Array2;
+
+use eigenvalues_problem::{
+    jacobi_method, parallel_jacobi_method, CyclicChoice, CyclicThreshold, MaxModule,
+};
+use matrices::Examples;
+
+const EPSILON: f64 = 1e-9;
+
+fn symmetric_random(n: usize) -> Array2<f64> {
+    let matrix = Examples::random_matrix(n) / 100.0;
+    &matrix + &matrix.t()
+}
+
+fn jacobi(c: &mut Criterion) {
+    let mut group = c.benchmark_group("jacobi");
+    group.sample_size(10);
+    for n in [50, 100, 200] {
+        let matrix = symmetric_random(n);
+        group.bench_with_input(BenchmarkId::new("MaxModule", n), &matrix, |b, matrix| {
+            b.iter(|| jacobi_method(matrix.view(), MaxModule::new(matrix.view()), EPSILON))
+        });
+        group.bench_with_input(BenchmarkId::new("CyclicChoice", n), &matrix, |b, matrix| {
+            b.iter(|| jacobi_method(matrix.view(), CyclicChoice::new(matrix.view()), EPSILON))
+        });
+        group.bench_with_input(
+            BenchmarkId::new("CyclicThreshold", n),
+            &matrix,
+            |b, matrix| {
+                b.iter(|| {
+                    jacobi_method(matrix.view(), CyclicThreshold::new(matrix.view()), EPSILON)
+                })
+            },
+        );
+        group.bench_with_input(BenchmarkId::new("parallel", n), &matrix, |b, matrix| {
+            b.iter(|| parallel_jacobi_method(matrix.view(), EPSILON))
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, jacobi);
+criterion_main!(benches);
@@ -6,35 +6,43 @@ use ndarray_linalg::Scalar;
 pub trait ChooseMax: Debug {
     fn choose(&mut self, matrix: ArrayView2<f64>) -> (usize, usize);
     fn update(&mut self, pos: (usize, usize), matrix: ArrayView2<f64>);
+
+    /// Sweeps started by a cyclic strategy, `None` for pivoting strategies.
+    fn sweeps(&self) -> Option<u32> {
+        None
+    }
+}
+
+/// Largest off-diagonal `|a_ij|`.
+pub struct MaxModule {
+    maxima: RowMaxima,
 }
 
-pub struct MaxModule;
+impl MaxModule {
+    pub fn new(matrix: ArrayView2<f64>) -> Self {
+        Self {
+            maxima: RowMaxima::new(matrix),
+        }
+    }
+}
 
 impl ChooseMax for MaxModule {
     fn choose(&mut self, matrix: ArrayView2<f64>) -> (usize, usize) {
-        let n = matrix.nrows();
-
         let mut max = f64::MIN;
         let mut max_index = (0, 0);
-
-        for i in 0..n {
-            for j in 0..n {
-                if i == j {
-                    continue;
-                }
-
-                if matrix[(i, j)].abs() > max {
-                    max = matrix[(i, j)].abs();
-                    //println!("max: {max:.2} at ({}, {})", i, j);
-                    max_index = (i, j);
-                }
+        for (i, &j) in self.maxima.0.iter().enumerate() {
+            if matrix[(i, j)].abs() > max {
+                max = matrix[(i, j)].abs();
+                max_index = (i, j);
             }
         }
 
         max_index
     }
 
-    fn update(&mut self, _: (usize, usize), _: ArrayView2<f64>) {}
+    fn update(&mut self, pos: (usize, usize), matrix: ArrayView2<f64>) {
+        self.maxima.update(pos, matrix);
+    }
 }
 
 impl Debug for MaxModule {
@@ -66,30 +74,28 @@ impl Debug for CyclicChoice {
 }
 
 impl ChooseMax for CyclicChoice {
+    /// Pairs `i < j` in row order: `(0, 1), (0, 2), ..., (n - 2, n - 1), (0, 1), ...`.
     fn choose(&mut self, _: ArrayView2<f64>) -> (usize, usize) {
-        self.j = (self.j + 1) % self.n;
-        if self.j == 0 {
-            self.i = (self.i + 1) % self.n;
+        self.j += 1;
+        if self.j == self.n {
+            self.i += 1;
+            self.j = self.i + 1;
         }
-
-        if self.i == self.j {
-            if self.i == self.n - 1 && self.j == self.n - 1 {
-                self.i = 0;
-                self.j = 1;
-            } else {
-                self.j = (self.j + 1) % self.n;
-            }
+        if self.j >= self.n {
+            self.i = 0;
+            self.j = 1;
         }
 
-        //println!("{}, {}", self.i, self.j);
         (self.i, self.j)
     }
 
     fn update(&mut self, _: (usize, usize), _: ArrayView2<f64>) {}
 }
 
+/// Largest `|a_ij|` in the row with the largest `Σ_{l≠i} a_il²`.
 pub struct OptimalElement {
     sums: Vec<f64>,
+    maxima: RowMaxima,
 }
 
 impl OptimalElement {
@@ -100,12 +106,17 @@ impl OptimalElement {
     }
 
     fn sum(row: ArrayView1<f64>, i: usize) -> f64 {
-        row.iter().map(|x| x.square()).sum::<f64>() - row[i].square()
+        row.iter()
+            .enumerate()
+            .filter(|&(l, _)| l != i)
+            .map(|(_, x)| x.square())
+            .sum()
     }
 
     pub fn new(matrix: ArrayView2<f64>) -> Self {
         Self {
             sums: Self::sums(matrix),
+            maxima: RowMaxima::new(matrix),
         }
     }
 }
@@ -117,9 +128,7 @@ impl Debug for OptimalElement {
 }
 
 impl ChooseMax for OptimalElement {
-    fn choose(&mut self, matrix: ArrayView2<f64>) -> (usize, usize) {
-        self.sums = Self::sums(matrix);
-
+    fn choose(&mut self, _: ArrayView2<f64>) -> (usize, usize) {
         let row_index = self
             .sums
             .iter()
@@ -127,27 +136,134 @@ impl ChooseMax for OptimalElement {
             .max_by(|(_, a), (_, b)| a.total_cmp(b))
             .map(|(index, _)| index)
             .unwrap();
-        //println!("row sums: {:?}", self.sums);
-        //println!("max row: {}", matrix.row(row_index));
 
-        let element_index = matrix
-            .row(row_index)
-            .iter()
-            .enumerate()
-            .filter(|(index, _)| *index != row_index)
-            .max_by(|(_, a), (_, b)| a.abs().total_cmp(&b.abs()))
-            .map(|(index, _)| index)
-            .unwrap();
-        //println!("max element in row: {}", matrix[(row_index, element_index)]);
+        (row_index, self.maxima.0[row_index])
+    }
 
-        //println!("{}, {}", row_index, element_index);
-        (row_index, element_index)
+    /// A rotation in the plane `(i, j)` keeps `a_ki² + a_kj²` for `k ≠ i, j`,
+    /// so only the sums of rows `i` and `j` change.
+    fn update(&mut self, (i, j): (usize, usize), matrix: ArrayView2<f64>) {
+        self.sums[i] = Self::sum(matrix.row(i), i);
+        self.sums[j] = Self::sum(matrix.row(j), j);
+        self.maxima.update((i, j), matrix);
     }
+}
+
+/// Column of the largest off-diagonal `|a_kl|` in every row `k`, the first one among equal.
+#[derive(Debug, Clone)]
+struct RowMaxima(Vec<usize>);
 
+impl RowMaxima {
+    fn new(matrix: ArrayView2<f64>) -> Self {
+        Self((0..matrix.nrows()).map(|k| Self::scan(matrix, k)).collect())
+    }
+
+    fn scan(matrix: ArrayView2<f64>, k: usize) -> usize {
+        let mut max = f64::MIN;
+        let mut max_index = k;
+        for (l, a) in matrix.row(k).iter().enumerate() {
+            if l != k && a.abs() > max {
+                max = a.abs();
+                max_index = l;
+            }
+        }
+        max_index
+    }
+
+    /// O(n) unless the maximum of a row sits in column `i` or `j`, then the row is rescanned.
     fn update(&mut self, (i, j): (usize, usize), matrix: ArrayView2<f64>) {
-        let i_row = Self::sum(matrix.row(i), i);
-        let j_row = Self::sum(matrix.row(j), j);
-        self.sums[i] = i_row;
-        self.sums[j] = j_row;
+        for (k, max_index) in self.0.iter_mut().enumerate() {
+            if k == i || k == j || *max_index == i || *max_index == j {
+                *max_index = Self::scan(matrix, k);
+                continue;
+            }
+            for column in [i.min(j), i.max(j)] {
+                let (a, max) = (matrix[(k, column)].abs(), matrix[(k, *max_index)].abs());
+                if a > max || (a == max && column < *max_index) {
+                    *max_index = column;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{Array2, ArrayView2};
+
+    use matrices::Examples;
+
+    use crate::{jacobi_method, ChooseMax, MaxModule, OptimalElement};
+
+    /// Pivot selection scanning the whole matrix every step.
+    #[derive(Debug)]
+    struct FullScan {
+        optimal: bool,
+    }
+
+    impl ChooseMax for FullScan {
+        fn choose(&mut self, matrix: ArrayView2<f64>) -> (usize, usize) {
+            let n = matrix.nrows();
+            let off_diagonal = |i: usize| (0..n).filter(move |&j| j != i);
+            let row_index = if self.optimal {
+                (0..n)
+                    .map(|i| off_diagonal(i).map(|j| matrix[(i, j)].powi(2)).sum::<f64>())
+                    .enumerate()
+                    .max_by(|(_, a), (_, b)| a.total_cmp(b))
+                    .map(|(index, _)| index)
+                    .unwrap()
+            } else {
+                (0..n)
+                    .map(|i| {
+                        off_diagonal(i)
+                            .map(|j| matrix[(i, j)].abs())
+                            .fold(0.0, f64::max)
+                    })
+                    .enumerate()
+                    .fold(
+                        (0, f64::MIN),
+                        |max, (i, a)| if a > max.1 { (i, a) } else { max },
+                    )
+                    .0
+            };
+            let column = off_diagonal(row_index)
+                .fold(None, |max: Option<usize>, j| match max {
+                    Some(m) if matrix[(row_index, m)].abs() >= matrix[(row_index, j)].abs() => {
+                        Some(m)
+                    }
+                    _ => Some(j),
+                })
+                .unwrap();
+            (row_index, column)
+        }
+
+        fn update(&mut self, _: (usize, usize), _: ArrayView2<f64>) {}
+    }
+
+    fn symmetric_random(n: usize) -> Array2<f64> {
+        let matrix = Examples::random_matrix(n) / 100.0;
+        &matrix + &matrix.t()
+    }
+
+    #[test]
+    fn incremental_pivoting_matches_full_scan() {
+        for matrix in [Examples::hilbert(10), symmetric_random(15)] {
+            let pairs = [
+                (
+                    jacobi_method(matrix.view(), MaxModule::new(matrix.view()), 1e-10),
+                    jacobi_method(matrix.view(), FullScan { optimal: false }, 1e-10),
+                ),
+                (
+                    jacobi_method(matrix.view(), OptimalElement::new(matrix.view()), 1e-10),
+                    jacobi_method(matrix.view(), FullScan { optimal: true }, 1e-10),
+                ),
+            ];
+            for (incremental, full) in pairs {
+                assert_eq!(incremental.steps, full.steps);
+                for (a, b) in incremental.eigenvalues.iter().zip(full.eigenvalues.iter()) {
+                    assert!((a - b).abs() < 1e-12);
+                }
+            }
+        }
     }
 }
@@ -0,0 +1,102 @@
+use std::fmt::{Debug, Formatter};
+
+use ndarray::ArrayView2;
+
+use matrices::Observer;
+
+use crate::{
+    jacobi_method, jacobi_method_observed, off_diagonal_sums, ChooseMax, JacobiResult, JacobiStep,
+};
+
+/// Sweeps with a positive threshold, as in the classical threshold Jacobi.
+const THRESHOLD_SWEEPS: u32 = 3;
+
+/// Cyclic-by-row threshold strategy: sweeps over all pairs `i < j` in row order.
+///
+/// During the first sweeps elements below `0.2 S / n²`, `S = Σ_{i<j} |a_ij|`,
+/// are skipped, later every nonzero element is annihilated.
+pub struct CyclicThreshold {
+    i: usize,
+    j: usize,
+    n: usize,
+    sweeps: u32,
+    threshold: f64,
+}
+
+impl CyclicThreshold {
+    pub fn new(matrix: ArrayView2<f64>) -> Self {
+        Self {
+            i: 0,
+            j: 0,
+            n: matrix.nrows(),
+            sweeps: 0,
+            threshold: 0.0,
+        }
+    }
+
+    /// Moves to the next pair, a new sweep recomputes the threshold.
+    fn advance(&mut self, matrix: ArrayView2<f64>) {
+        self.j += 1;
+        if self.j >= self.n {
+            self.i += 1;
+            self.j = self.i + 1;
+        }
+        if self.j >= self.n || self.sweeps == 0 {
+            self.i = 0;
+            self.j = 1;
+            self.sweeps += 1;
+            self.threshold = if self.sweeps <= THRESHOLD_SWEEPS {
+                let sum = off_diagonal_sums(matrix).iter().sum::<f64>() / 2.0;
+                0.2 * sum / (self.n * self.n) as f64
+            } else {
+                0.0
+            };
+        }
+    }
+}
+
+impl Debug for CyclicThreshold {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "CyclicThreshold")
+    }
+}
+
+impl ChooseMax for CyclicThreshold {
+    /// The next pair above the threshold; after a whole sweep without a nonzero element
+    /// past the threshold sweeps the pair reached is returned, its rotation is the identity.
+    fn choose(&mut self, matrix: ArrayView2<f64>) -> (usize, usize) {
+        let pairs = self.n * self.n.saturating_sub(1) / 2;
+        let limit = (THRESHOLD_SWEEPS as usize + 1) * pairs;
+        for _ in 0..limit {
+            self.advance(matrix);
+            let element = matrix[(self.i, self.j)].abs();
+            if element != 0.0 && element > self.threshold {
+                break;
+            }
+        }
+        (self.i, self.j)
+    }
+
+    fn update(&mut self, _: (usize, usize), _: ArrayView2<f64>) {}
+
+    fn sweeps(&self) -> Option<u32> {
+        Some(self.sweeps)
+    }
+}
+
+/// [`jacobi_method`] with the [`CyclicThreshold`] strategy.
+pub fn cyclic_jacobi_method(matrix: ArrayView2<f64>, epsilon: f64) -> JacobiResult {
+    jacobi_method(matrix, CyclicThreshold::new(matrix), epsilon)
+}
+
+/// [`cyclic_jacobi_method`] reporting every rotation to `observer`.
+pub fn cyclic_jacobi_method_observed<O>(
+    matrix: ArrayView2<f64>,
+    epsilon: f64,
+    observer: &mut O,
+) -> JacobiResult
+where
+    O: for<'a> Observer<JacobiStep<'a>>,
+{
+    jacobi_method_observed(matrix, CyclicThreshold::new(matrix), epsilon, observer)
+}
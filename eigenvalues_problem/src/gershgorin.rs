@@ -1,49 +1,337 @@
 use std::fmt::{Display, Formatter};
 
-use ndarray::ArrayView2;
+use ndarray::{ArrayView1, ArrayView2, Axis};
+use ndarray_linalg::c64;
 
-struct Circle {
+const ISOLATION_ITERATIONS: usize = 100;
+
+/// Disc `|z - center| <= radius` in the complex plane.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Disc {
     center: f64,
     radius: f64,
 }
 
-pub struct Circles(Vec<Circle>);
+impl Disc {
+    pub fn center(&self) -> f64 {
+        self.center
+    }
+
+    pub fn radius(&self) -> f64 {
+        self.radius
+    }
+
+    pub fn contains(&self, z: c64) -> bool {
+        (z - self.center).norm() <= self.radius
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum DiscKind {
+    /// `R_i = Σ_{j≠i} |a_ij|`.
+    Row,
+    /// `C_j = Σ_{i≠j} |a_ij|`.
+    Column,
+}
+
+/// Connected component of the union of the discs of one kind,
+/// it contains as many eigenvalues as discs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Component {
+    discs: Vec<usize>,
+    left: f64,
+    right: f64,
+}
+
+impl Component {
+    /// Indices of the discs.
+    pub fn discs(&self) -> &[usize] {
+        &self.discs
+    }
+
+    pub fn eigenvalues(&self) -> usize {
+        self.discs.len()
+    }
+
+    /// Intersection with the real axis.
+    pub fn interval(&self) -> (f64, f64) {
+        (self.left, self.right)
+    }
+}
+
+/// Rectangle containing every eigenvalue.
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Bounds {
+    pub real: (f64, f64),
+    /// Bound on `|Im λ|`.
+    pub imaginary: f64,
+}
+
+/// Row and column Gershgorin discs.
+/// Every eigenvalue lies in the union of the row discs and in the union of the column discs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Circles {
+    rows: Vec<Disc>,
+    columns: Vec<Disc>,
+}
 
 impl Circles {
     pub fn new(matrix: ArrayView2<f64>) -> Self {
         let n = matrix.nrows();
+        let discs = |radii: Vec<f64>| {
+            (0..n)
+                .zip(radii)
+                .map(|(i, radius)| Disc {
+                    center: matrix[(i, i)],
+                    radius,
+                })
+                .collect()
+        };
 
-        let centers = (0..n).map(|i| matrix[(i, i)]).collect::<Vec<_>>();
-        let radii = Self::radii(matrix);
+        Self {
+            rows: discs(Self::radii(matrix)),
+            columns: discs(Self::radii(matrix.t())),
+        }
+    }
 
-        let circles = centers
-            .into_iter()
-            .zip(radii.iter())
-            .map(|(center, &radius)| Circle { center, radius })
-            .collect();
-        Self(circles)
+    /// Discs of the similar matrix `D⁻¹ A D`, `D = diag(scaling)`.
+    pub fn scaled(matrix: ArrayView2<f64>, scaling: ArrayView1<f64>) -> Self {
+        let scaled =
+            matrix.to_owned() * scaling.insert_axis(Axis(0)) / scaling.insert_axis(Axis(1));
+        Self::new(scaled.view())
     }
 
     fn radii(matrix: ArrayView2<f64>) -> Vec<f64> {
         let n = matrix.nrows();
         (0..n)
-            .map(|i| matrix.row(i).map(|x| x.abs()).sum() - matrix[(i, i)])
+            .map(|i| matrix.row(i).map(|x| x.abs()).sum() - matrix[(i, i)].abs())
             .collect()
     }
+
+    /// Row disc `i` of `D⁻¹ A D`, `D = diag(1, ..., 1/α, ..., 1)` with `1/α` at `i`,
+    /// for the smallest `α ∈ (0, 1]` keeping it disjoint from the other row discs.
+    /// Such a disc contains exactly one eigenvalue.
+    /// `None` when no `α` separates the disc.
+    ///
+    /// The radius of disc `i` becomes `α R_i` and the others `R_k + |a_ki| (1/α - 1)`,
+    /// the separation `|a_ii - a_kk| - α R_i - R_k - |a_ki| (1/α - 1)` is concave in `α`,
+    /// so its maximum is found by ternary search and the smallest separating `α` by bisection.
+    pub fn isolate(matrix: ArrayView2<f64>, i: usize) -> Option<Disc> {
+        let n = matrix.nrows();
+        let radii = Self::radii(matrix);
+        let separation = |alpha: f64| {
+            (0..n)
+                .filter(|&k| k != i)
+                .map(|k| {
+                    let coupling = matrix[(k, i)].abs();
+                    (matrix[(i, i)] - matrix[(k, k)]).abs()
+                        - alpha * radii[i]
+                        - radii[k]
+                        - coupling * (1.0 / alpha - 1.0)
+                })
+                .fold(f64::INFINITY, f64::min)
+        };
+
+        let (mut left, mut right) = (f64::MIN_POSITIVE, 1.0);
+        for _ in 0..ISOLATION_ITERATIONS {
+            let (a, b) = (left + (right - left) / 3.0, right - (right - left) / 3.0);
+            if separation(a) < separation(b) {
+                left = a;
+            } else {
+                right = b;
+            }
+        }
+        let best = (left + right) / 2.0;
+        if separation(best) <= 0.0 {
+            return None;
+        }
+
+        let (mut left, mut right) = (f64::MIN_POSITIVE, best);
+        for _ in 0..ISOLATION_ITERATIONS {
+            let middle = (left + right) / 2.0;
+            if separation(middle) > 0.0 {
+                right = middle;
+            } else {
+                left = middle;
+            }
+        }
+        Some(Disc {
+            center: matrix[(i, i)],
+            radius: right * radii[i],
+        })
+    }
+
+    pub fn rows(&self) -> &[Disc] {
+        &self.rows
+    }
+
+    pub fn columns(&self) -> &[Disc] {
+        &self.columns
+    }
+
+    fn discs(&self, kind: DiscKind) -> &[Disc] {
+        match kind {
+            DiscKind::Row => &self.rows,
+            DiscKind::Column => &self.columns,
+        }
+    }
+
+    /// `z` lies in the intersection of the unions of the row and the column discs.
+    pub fn contains(&self, z: c64) -> bool {
+        self.rows.iter().any(|disc| disc.contains(z))
+            && self.columns.iter().any(|disc| disc.contains(z))
+    }
+
+    pub fn bounds(&self) -> Bounds {
+        let segment = |discs: &[Disc]| {
+            discs
+                .iter()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(left, right), disc| {
+                    (
+                        left.min(disc.center - disc.radius),
+                        right.max(disc.center + disc.radius),
+                    )
+                })
+        };
+        let largest = |discs: &[Disc]| discs.iter().map(|disc| disc.radius).fold(0.0, f64::max);
+
+        let (rows, columns) = (segment(&self.rows), segment(&self.columns));
+        Bounds {
+            real: (rows.0.max(columns.0), rows.1.min(columns.1)),
+            imaginary: largest(&self.rows).min(largest(&self.columns)),
+        }
+    }
+
+    /// Components in the order of their intervals on the real axis.
+    /// Centers are real, so discs intersect exactly when their real segments do.
+    pub fn components(&self, kind: DiscKind) -> Vec<Component> {
+        let discs = self.discs(kind);
+        let mut order = (0..discs.len()).collect::<Vec<_>>();
+        order.sort_by(|&a, &b| {
+            let left = |disc: &Disc| disc.center - disc.radius;
+            left(&discs[a]).total_cmp(&left(&discs[b]))
+        });
+
+        let mut components: Vec<Component> = Vec::new();
+        for index in order {
+            let disc = discs[index];
+            match components.last_mut() {
+                Some(component) if disc.center - disc.radius <= component.right => {
+                    component.discs.push(index);
+                    component.right = component.right.max(disc.center + disc.radius);
+                }
+                _ => components.push(Component {
+                    discs: vec![index],
+                    left: disc.center - disc.radius,
+                    right: disc.center + disc.radius,
+                }),
+            }
+        }
+        for component in &mut components {
+            component.discs.sort();
+        }
+        components
+    }
 }
 
 impl Display for Circles {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        let print = self
-            .0
+        for (kind, name) in [(DiscKind::Row, "rows"), (DiscKind::Column, "columns")] {
+            writeln!(f, "{name}:")?;
+            for disc in self.discs(kind) {
+                writeln!(f, "|z - {:.2}| <= {:.2}", disc.center, disc.radius)?;
+            }
+            for component in self.components(kind) {
+                let (left, right) = component.interval();
+                writeln!(
+                    f,
+                    "{left:.2} <= Re z <= {right:.2}: {} eigenvalue(s)",
+                    component.eigenvalues()
+                )?;
+            }
+        }
+        let bounds = self.bounds();
+        writeln!(
+            f,
+            "{:.2} <= Re λ <= {:.2}, |Im λ| <= {:.2}",
+            bounds.real.0, bounds.real.1, bounds.imaginary
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::array;
+    use ndarray_linalg::{c64, EigVals};
+
+    use crate::{Circles, DiscKind};
+
+    #[test]
+    fn radii_of_negative_diagonal() {
+        let matrix = array![[-5.0, 1.0, -0.5], [2.0, 1.0, 0.0], [0.0, -0.25, -1.0]];
+        let circles = Circles::new(matrix.view());
+
+        let rows = circles
+            .rows()
+            .iter()
+            .map(|disc| disc.radius())
+            .collect::<Vec<_>>();
+        let columns = circles
+            .columns()
             .iter()
-            .map(|circle| {
-                let left = circle.center - circle.radius;
-                let right = circle.center + circle.radius;
-                format!("{left:.2} <= z <= {right:.2}\n")
-            })
-            .collect::<Vec<_>>()
-            .concat();
-        write!(f, "{print}")
+            .map(|disc| disc.radius())
+            .collect::<Vec<_>>();
+        assert_eq!(rows, vec![1.5, 2.0, 0.25]);
+        assert_eq!(columns, vec![2.0, 1.25, 0.5]);
+    }
+
+    #[test]
+    fn components_count_eigenvalues() {
+        let matrix = array![
+            [10.0, 1.0, 0.5, 0.0],
+            [-1.0, 9.0, 0.0, 0.5],
+            [0.2, 0.0, -4.0, 1.0],
+            [0.0, 0.3, -2.0, -3.0],
+        ];
+        let circles = Circles::new(matrix.view());
+        let eigenvalues = matrix.eigvals().unwrap();
+
+        for kind in [DiscKind::Row, DiscKind::Column] {
+            let components = circles.components(kind);
+            assert_eq!(components.len(), 2);
+            for component in components {
+                let (left, right) = component.interval();
+                let inside = eigenvalues
+                    .iter()
+                    .filter(|lambda| left <= lambda.re && lambda.re <= right)
+                    .count();
+                assert_eq!(inside, component.eigenvalues());
+            }
+        }
+
+        let bounds = circles.bounds();
+        for lambda in eigenvalues.iter() {
+            assert!(circles.contains(*lambda));
+            assert!(bounds.real.0 <= lambda.re && lambda.re <= bounds.real.1);
+            assert!(lambda.im.abs() <= bounds.imaginary);
+        }
+        assert!(!circles.contains(c64::new(3.0, 0.0)));
+    }
+
+    #[test]
+    fn scaling_isolates_eigenvalue() {
+        let matrix = array![[1.0, 0.1, 0.1], [0.1, 2.0, 0.1], [0.1, 0.1, 3.0]];
+        let eigenvalues = matrix.eigvals().unwrap();
+        for i in 0..3 {
+            let disc = Circles::isolate(matrix.view(), i).unwrap();
+            assert!(disc.radius() < Circles::new(matrix.view()).rows()[i].radius());
+            let inside = eigenvalues.iter().filter(|&&lambda| disc.contains(lambda));
+            assert_eq!(inside.count(), 1);
+        }
+
+        let scaling = array![1.0, 10.0, 1.0];
+        let scaled = Circles::scaled(matrix.view(), scaling.view());
+        assert!((scaled.rows()[1].radius() - 0.02).abs() < 1e-15);
+        assert!((scaled.rows()[0].radius() - 1.1).abs() < 1e-15);
     }
 }
@@ -1,80 +1,544 @@
 use std::fmt::{Display, Formatter};
 
-use ndarray::{Array2, ArrayView2};
-use ndarray_linalg::Inverse;
+use ndarray::{s, Array1, Array2, ArrayView1, ArrayView2, ArrayViewMut1, Zip};
+
+use matrices::Observer;
 
 pub use choose_max::*;
+pub use cyclic::*;
 pub use gershgorin::*;
-use matrices::rotation_matrix_default;
+pub use parallel::*;
 
 mod choose_max;
+mod cyclic;
 mod gershgorin;
+mod parallel;
+
+/// Rotations are stopped after this many sweeps, or `n(n - 1) / 2` rotations each.
+const MAX_SWEEPS: u32 = 50;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct JacobiResult {
     eigenvalues: Vec<f64>,
+    /// Column `k` belongs to `eigenvalues[k]`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::matrix"))]
+    eigenvectors: Array2<f64>,
+    /// Rotations.
     steps: u32,
+    sweeps: u32,
+    off_diagonal: f64,
+    converged: bool,
 }
 
-fn rotation_matrix((i, j): (usize, usize), matrix: ArrayView2<f64>) -> Array2<f64> {
-    let x = -2.0 * matrix[(i, j)];
-    let y = matrix[(i, i)] - matrix[(j, j)];
+impl JacobiResult {
+    pub fn eigenvalues(&self) -> &[f64] {
+        &self.eigenvalues
+    }
 
-    let angle = (x / y).atan() / 2.0;
-    let cos = angle.cos();
-    let sin = angle.sin();
-    // let (cos, sin) = if y < f64::EPSILON {
-    //     let value = 1.0 / 2.0_f64.sqrt();
-    //     (value, value)
-    // } else {
-    //     let distance = (x * x + y * y).sqrt();
-    //     let cos = ((1.0 + y.abs() / distance) / 2.0).sqrt();
-    //     let sin = x.abs() * (x * y).signum() / (2.0 * cos * distance);
-    //     (cos, sin)
-    // };
+    /// Rotations.
+    pub fn steps(&self) -> u32 {
+        self.steps
+    }
 
-    let n = matrix.nrows();
-    rotation_matrix_default(n, (cos, sin), (i, j))
+    /// Sweeps of a cyclic method; for pivoting strategies the number of rotations
+    /// in units of `n(n - 1) / 2`, rounded up.
+    pub fn sweeps(&self) -> u32 {
+        self.sweeps
+    }
+
+    pub fn eigenvectors(&self) -> ArrayView2<'_, f64> {
+        self.eigenvectors.view()
+    }
+
+    /// Frobenius norm of the off-diagonal part of the final matrix.
+    pub fn off_diagonal(&self) -> f64 {
+        self.off_diagonal
+    }
+
+    /// `false` when the method stopped after `MAX_SWEEPS` sweeps with `R_k >= epsilon` for some row.
+    pub fn converged(&self) -> bool {
+        self.converged
+    }
+}
+
+/// The matrix after a rotation, or after a round of [`parallel_jacobi_method_observed`].
+#[derive(Debug, Copy, Clone)]
+pub struct JacobiStep<'a> {
+    /// Rotations so far.
+    pub step: u32,
+    matrix: ArrayView2<'a, f64>,
+}
+
+impl<'a> JacobiStep<'a> {
+    /// Eigenvalue estimates.
+    pub fn diagonal(&self) -> ArrayView1<'a, f64> {
+        self.matrix.into_diag()
+    }
+
+    /// Frobenius norm of the off-diagonal part, computed on demand in O(n²).
+    pub fn off_diagonal(&self) -> f64 {
+        off_diagonal_norm(self.matrix)
+    }
+
+    pub fn matrix(&self) -> ArrayView2<'a, f64> {
+        self.matrix
+    }
+
+    /// An owned copy of the estimates, the step itself borrows the matrix being rotated.
+    pub fn to_record(&self) -> JacobiRecord {
+        JacobiRecord {
+            step: self.step,
+            diagonal: self.diagonal().to_owned(),
+            off_diagonal: self.off_diagonal(),
+        }
+    }
+}
+
+/// [`JacobiStep`] without the matrix, collected by a `Vec<JacobiRecord>` observer.
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+pub struct JacobiRecord {
+    pub step: u32,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
+    pub diagonal: Array1<f64>,
+    pub off_diagonal: f64,
+}
+
+/// A `Vec<JacobiStep>` cannot outlive the rotations, the owned records can.
+impl<'a> Observer<JacobiStep<'a>> for Vec<JacobiRecord> {
+    fn observe(&mut self, step: JacobiStep<'a>) {
+        self.push(step.to_record());
+    }
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for JacobiResult {
+    type Record = matrices::export::ComponentRecord;
+
+    fn records(&self) -> Vec<Self::Record> {
+        matrices::export::components(&self.eigenvalues)
+    }
+}
+
+/// Cosine and sine of the rotation annihilating `a_ij`, Rutishauser's formulation:
+/// `θ = cot 2φ = (a_jj - a_ii) / (2 a_ij)`, `t = tan φ = sgn(θ) / (|θ| + √(θ² + 1))`,
+/// the smaller of the two angles, `|φ| ≤ π/4`.
+/// Equal diagonal entries give `θ = 0` and exactly `φ = π/4`.
+fn rotation((i, j): (usize, usize), matrix: ArrayView2<f64>) -> (f64, f64) {
+    let a = matrix[(i, j)];
+    if a == 0.0 {
+        return (1.0, 0.0);
+    }
+
+    let theta = (matrix[(j, j)] - matrix[(i, i)]) / (2.0 * a);
+    let t = if theta == 0.0 {
+        1.0
+    } else if theta.abs() > 1e150 {
+        // θ² overflows, t ≈ 1 / 2θ
+        0.5 / theta
+    } else {
+        theta.signum() / (theta.abs() + (theta * theta + 1.0).sqrt())
+    };
+    let cos = 1.0 / (1.0 + t * t).sqrt();
+    (cos, t * cos)
+}
+
+/// `A <- R A Rᵀ` for the rotation `R` in the plane `(i, j)`,
+/// only rows and columns `i` and `j` change.
+fn rotate(matrix: &mut Array2<f64>, (i, j): (usize, usize), (cos, sin): (f64, f64)) {
+    let (row_i, row_j) = matrix.multi_slice_mut((s![i, ..], s![j, ..]));
+    rotate_lanes(row_i, row_j, (cos, sin));
+    rotate_columns(matrix, (i, j), (cos, sin));
+}
+
+/// `A <- A Rᵀ`.
+fn rotate_columns(matrix: &mut Array2<f64>, (i, j): (usize, usize), (cos, sin): (f64, f64)) {
+    let (column_i, column_j) = matrix.multi_slice_mut((s![.., i], s![.., j]));
+    rotate_lanes(column_i, column_j, (cos, sin));
+}
+
+fn rotate_lanes(a: ArrayViewMut1<f64>, b: ArrayViewMut1<f64>, (cos, sin): (f64, f64)) {
+    Zip::from(a).and(b).for_each(|a, b| {
+        let (x, y) = (*a, *b);
+        *a = cos * x - sin * y;
+        *b = sin * x + cos * y;
+    });
 }
 
 pub fn jacobi_method<C: ChooseMax>(
     matrix: ArrayView2<f64>,
-    mut finder: C,
+    finder: C,
     epsilon: f64,
 ) -> JacobiResult {
+    jacobi_method_observed(matrix, finder, epsilon, &mut ())
+}
+
+/// [`jacobi_method`] reporting every rotation to `observer`.
+/// Stops when `R_k = Σ_{l≠k} |a_kl| < epsilon` or `R_k = 0` for every row,
+/// Stops when `R_k = Σ_{l≠k} |a_kl| < epsilon` for every row,
+/// or unconverged after `MAX_SWEEPS` sweeps of `n(n - 1) / 2` rotations.
+pub fn jacobi_method_observed<C, O>(
+    matrix: ArrayView2<f64>,
+    mut finder: C,
+    epsilon: f64,
+    observer: &mut O,
+) -> JacobiResult
+where
+    C: ChooseMax,
+    O: for<'a> Observer<JacobiStep<'a>>,
+{
     let n = matrix.nrows();
+    let pairs = (n * n.saturating_sub(1) / 2).max(1) as u32;
 
     let mut steps = 0;
     let mut matrix = matrix.to_owned();
-    while !is_diagonal_new(matrix.view(), epsilon) {
+    let mut eigenvectors = Array2::eye(n);
+    let mut sums = OffDiagonalSums::new(matrix.view());
+    let converged = loop {
+        if sums.below(matrix.view(), epsilon) {
+            break true;
+        }
+        if steps == MAX_SWEEPS * pairs {
+            break false;
+        }
         let max = finder.choose(matrix.view());
 
-        let rotation_matrix = rotation_matrix(max, matrix.view());
-        let rotation_matrix_inv = rotation_matrix.inv().unwrap();
-        matrix = rotation_matrix.dot(&matrix).dot(&rotation_matrix_inv);
+        let rotation = rotation(max, matrix.view());
+        sums.remove(matrix.view(), max);
+        rotate(&mut matrix, max, rotation);
+        rotate_columns(&mut eigenvectors, max, rotation);
+        sums.add(matrix.view(), max);
 
         steps += 1;
-        //println!("{matrix:.2}");
-        // finder.update(max, matrix.view());
+        finder.update(max, matrix.view());
+        observer.observe(JacobiStep {
+            step: steps,
+            matrix: matrix.view(),
+        });
+    };
+
+    JacobiResult {
+        eigenvalues: (0..n).map(|i| matrix[(i, i)]).collect(),
+        eigenvectors,
+        steps,
+        sweeps: finder.sweeps().unwrap_or(steps.div_ceil(pairs)),
+        off_diagonal: off_diagonal_norm(matrix.view()),
+        converged,
     }
+}
 
-    let eigenvalues = (0..n).map(|i| matrix[(i, i)]).collect();
-    JacobiResult { eigenvalues, steps }
+/// `R_k < epsilon`, or a row that is already diagonal, so that `epsilon <= 0`
+/// still stops on a diagonal matrix instead of rotating a pair `(k, k)`.
+fn row_converged(sum: f64, epsilon: f64) -> bool {
+    sum < epsilon || sum == 0.0
 }
 
-fn is_diagonal_new(matrix: ArrayView2<f64>, epsilon: f64) -> bool {
-    let sums = matrix
+/// `R_k = Σ_{l≠k} |a_kl|` for every row.
+fn off_diagonal_sums(matrix: ArrayView2<f64>) -> Vec<f64> {
+    matrix
         .rows()
         .into_iter()
         .enumerate()
-        .map(|(i, row)| row.map(|x| x.abs()).sum() - row[i].abs())
-        .collect::<Vec<_>>();
-    //println!("R_i: {sum:?}");
-    sums.iter().all(|sum| *sum < epsilon)
+        .map(|(k, row)| row.iter().map(|x| x.abs()).sum::<f64>() - row[k].abs())
+        .collect()
+}
+
+/// Frobenius norm of the off-diagonal part.
+fn off_diagonal_norm(matrix: ArrayView2<f64>) -> f64 {
+    matrix
+        .indexed_iter()
+        .filter(|((row, column), _)| row != column)
+        .map(|(_, a)| a * a)
+        .sum::<f64>()
+        .sqrt()
+}
+
+/// Sums `R_k = Σ_{l≠k} |a_kl|`, kept up to date in O(n) per rotation
+/// and recomputed every `n` rotations against the accumulated rounding errors.
+struct OffDiagonalSums {
+    sums: Vec<f64>,
+    updates: usize,
+}
+
+impl OffDiagonalSums {
+    fn new(matrix: ArrayView2<f64>) -> Self {
+        Self {
+            sums: off_diagonal_sums(matrix),
+            updates: 0,
+        }
+    }
+
+    /// Called with the entries of rows and columns `i` and `j` before the rotation.
+    fn remove(&mut self, matrix: ArrayView2<f64>, pos: (usize, usize)) {
+        self.change(matrix, pos, -1.0);
+    }
+
+    /// Called with the entries of rows and columns `i` and `j` after the rotation.
+    fn add(&mut self, matrix: ArrayView2<f64>, pos: (usize, usize)) {
+        self.updates += 1;
+        if self.updates == self.sums.len() {
+            *self = Self::new(matrix);
+        } else {
+            self.change(matrix, pos, 1.0);
+        }
+    }
+
+    fn change(&mut self, matrix: ArrayView2<f64>, (i, j): (usize, usize), sign: f64) {
+        for (k, sum) in self.sums.iter_mut().enumerate() {
+            let change = if k == i || k == j {
+                let row = matrix.row(k);
+                row.iter().map(|x| x.abs()).sum::<f64>() - row[k].abs()
+            } else {
+                matrix[(k, i)].abs() + matrix[(k, j)].abs()
+            };
+            *sum += sign * change;
+        }
+    }
+
+    /// A positive answer is confirmed by recomputing the sums.
+    fn below(&mut self, matrix: ArrayView2<f64>, epsilon: f64) -> bool {
+        if !self.sums.iter().all(|&sum| row_converged(sum, epsilon)) {
+            return false;
+        }
+        *self = Self::new(matrix);
+        self.sums.iter().all(|&sum| row_converged(sum, epsilon))
+    }
 }
 
 impl Display for JacobiResult {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{:.2?} in {} steps", self.eigenvalues, self.steps)
+        write!(
+            f,
+            "{:.2?} in {} steps ({} sweeps)",
+            self.eigenvalues, self.steps, self.sweeps
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1, Array2, ArrayView2};
+
+    use matrices::Examples;
+
+    use crate::{
+        cyclic_jacobi_method, cyclic_jacobi_method_observed, jacobi_method, jacobi_method_observed,
+        parallel_jacobi_method, parallel_jacobi_method_observed, rotate, rotation, CyclicChoice,
+        CyclicThreshold, JacobiRecord, JacobiResult, JacobiStep, MaxModule, OptimalElement,
+    };
+
+    fn all_strategies(matrix: ArrayView2<f64>) -> Vec<JacobiResult> {
+        all_strategies_with(matrix, 1e-12)
+    }
+
+    fn all_strategies_with(matrix: ArrayView2<f64>, epsilon: f64) -> Vec<JacobiResult> {
+        vec![
+            jacobi_method(matrix, MaxModule::new(matrix), epsilon),
+            jacobi_method(matrix, OptimalElement::new(matrix), epsilon),
+            jacobi_method(matrix, CyclicChoice::new(matrix), epsilon),
+            jacobi_method(matrix, CyclicThreshold::new(matrix), epsilon),
+        ]
+    }
+
+    fn assert_eigenvalues(matrix: ArrayView2<f64>, expected: &[f64]) {
+        for result in all_strategies(matrix) {
+            let mut eigenvalues = result.eigenvalues.clone();
+            eigenvalues.sort_by(f64::total_cmp);
+            for (a, b) in eigenvalues.iter().zip(expected) {
+                assert!((a - b).abs() < 1e-12, "{result}");
+            }
+            assert!(result.converged());
+            let vectors = result.eigenvectors();
+            let n = matrix.nrows();
+            assert!(vectors
+                .t()
+                .dot(&vectors)
+                .abs_diff_eq(&Array2::eye(n), 1e-12));
+        }
+    }
+
+    #[test]
+    fn rotation_with_equal_diagonal_entries() {
+        let mut matrix = array![[2.0, 1.0], [1.0, 2.0]];
+        let (cos, sin) = rotation((0, 1), matrix.view());
+        assert_eq!(cos, sin.abs());
+        assert!((cos - std::f64::consts::FRAC_1_SQRT_2).abs() <= f64::EPSILON);
+
+        rotate(&mut matrix, (0, 1), (cos, sin));
+        assert!(matrix[(0, 1)].abs() < 1e-15);
+        assert!(matrix[(1, 0)].abs() < 1e-15);
+    }
+
+    #[test]
+    fn repeated_diagonal_entries() {
+        let root = 2.0_f64.sqrt();
+        let tridiagonal = array![[2.0, 1.0, 0.0], [1.0, 2.0, 1.0], [0.0, 1.0, 2.0]];
+        assert_eigenvalues(tridiagonal.view(), &[2.0 - root, 2.0, 2.0 + root]);
+
+        let ones = Array2::from_elem((4, 4), 1.0) + Array2::<f64>::eye(4) * 2.0;
+        assert_eigenvalues(ones.view(), &[2.0, 2.0, 2.0, 6.0]);
+    }
+
+    #[test]
+    fn already_diagonal_blocks() {
+        let matrix = array![
+            [5.0, 0.0, 0.0, 0.0],
+            [0.0, 2.0, 0.0, 1.0],
+            [0.0, 0.0, -1.0, 0.0],
+            [0.0, 1.0, 0.0, 2.0],
+        ];
+        assert_eigenvalues(matrix.view(), &[-1.0, 1.0, 3.0, 5.0]);
+
+        let diagonal = Array2::from_diag(&array![3.0, -2.0, 3.0]);
+        for result in all_strategies(diagonal.view()) {
+            assert_eq!(result.steps, 0);
+            assert_eq!(result.eigenvalues, vec![3.0, -2.0, 3.0]);
+        }
+    }
+
+    #[test]
+    fn zero_matrix() {
+        let matrix = Array2::zeros((3, 3));
+        assert_eq!(rotation((0, 1), matrix.view()), (1.0, 0.0));
+        for result in all_strategies(matrix.view()) {
+            assert_eq!(result.steps, 0);
+            assert_eq!(result.eigenvalues, vec![0.0; 3]);
+            assert_eq!(result.eigenvectors(), Array2::<f64>::eye(3));
+        }
+    }
+
+    #[test]
+    fn empty_matrix() {
+        let matrix = Array2::zeros((0, 0));
+        for result in all_strategies(matrix.view()) {
+            assert_eq!(result.steps, 0);
+            assert!(result.eigenvalues.is_empty());
+            assert!(result.converged());
+        }
+    }
+
+    #[test]
+    fn single_element_and_diagonal_matrices_with_zero_epsilon() {
+        for matrix in [array![[3.0]], array![[3.0, 0.0], [0.0, -1.0]]] {
+            let mut results = all_strategies_with(matrix.view(), 0.0);
+            results.push(parallel_jacobi_method(matrix.view(), 0.0));
+            for result in results {
+                assert_eq!(result.steps, 0);
+                assert_eq!(result.eigenvalues, matrix.diag().to_vec());
+                assert!(result.converged());
+            }
+        }
+    }
+
+    #[test]
+    fn max_sweeps_without_convergence() {
+        // R_k < 0 never holds
+        let matrix = Examples::hilbert(5);
+        let mut results = all_strategies_with(matrix.view(), 0.0);
+        results.push(parallel_jacobi_method(matrix.view(), 0.0));
+        for result in results {
+            assert!(!result.converged());
+            assert!(result.off_diagonal() < 1e-15);
+        }
+    }
+
+    #[test]
+    fn eigenvectors_diagonalise_hilbert_matrix() {
+        let matrix = Examples::hilbert(8);
+        let result = jacobi_method(matrix.view(), MaxModule::new(matrix.view()), 1e-12);
+
+        let vectors = result.eigenvectors();
+        assert!(vectors
+            .t()
+            .dot(&vectors)
+            .abs_diff_eq(&Array2::eye(8), 1e-12));
+
+        let diagonal = Array2::from_diag(&Array1::from(result.eigenvalues.clone()));
+        let product = vectors.dot(&diagonal).dot(&vectors.t());
+        assert!(product.abs_diff_eq(&matrix, 1e-11));
+        assert!(result.off_diagonal() < 1e-11);
+    }
+
+    #[test]
+    fn cyclic_threshold_jacobi_matches_pivoting() {
+        let matrix = Examples::hilbert(12);
+        let pivoting = jacobi_method(matrix.view(), MaxModule::new(matrix.view()), 1e-12);
+        let cyclic = cyclic_jacobi_method(matrix.view(), 1e-12);
+
+        let mut expected = pivoting.eigenvalues.clone();
+        let mut eigenvalues = cyclic.eigenvalues.clone();
+        expected.sort_by(f64::total_cmp);
+        eigenvalues.sort_by(f64::total_cmp);
+        for (a, b) in eigenvalues.iter().zip(expected.iter()) {
+            assert!((a - b).abs() < 1e-12);
+        }
+        assert!(cyclic.sweeps() < 10);
+        assert!(cyclic.steps <= 66 * cyclic.sweeps());
+    }
+
+    #[test]
+    fn observer_traces_off_diagonal_norm() {
+        let matrix = Examples::hilbert(8);
+        let mut records: Vec<JacobiRecord> = Vec::new();
+        let (mut cyclic, mut parallel) = (Vec::new(), Vec::new());
+        let results = [
+            jacobi_method_observed(
+                matrix.view(),
+                MaxModule::new(matrix.view()),
+                1e-12,
+                &mut records,
+            ),
+            cyclic_jacobi_method_observed(matrix.view(), 1e-12, &mut |step: JacobiStep| {
+                cyclic.push((step.step, step.off_diagonal()))
+            }),
+            parallel_jacobi_method_observed(matrix.view(), 1e-12, &mut |step: JacobiStep| {
+                parallel.push((step.step, step.off_diagonal()))
+            }),
+        ];
+        let pivoting = records
+            .iter()
+            .map(|record| (record.step, record.off_diagonal))
+            .collect::<Vec<_>>();
+
+        assert_eq!(pivoting.len() as u32, results[0].steps());
+        assert_eq!(
+            records.last().unwrap().diagonal.to_vec(),
+            results[0].eigenvalues()
+        );
+        for (trace, result) in [pivoting, cyclic, parallel].iter().zip(&results) {
+            // a rotation lowers the squared norm by exactly 2 a_ij²
+            for pair in trace.windows(2) {
+                assert!(pair[0].0 < pair[1].0);
+                assert!(pair[1].1 <= pair[0].1 + 1e-15);
+            }
+            let &(steps, off_diagonal) = trace.last().unwrap();
+            assert_eq!(steps, result.steps());
+            assert_eq!(off_diagonal, result.off_diagonal());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn jacobi_result_to_json() {
+        use matrices::export::Export;
+
+        let matrix = array![[2.0, 0.0], [0.0, 1.0]];
+        let result = jacobi_method(matrix.view(), MaxModule::new(matrix.view()), 1e-12);
+
+        let mut json = Vec::new();
+        result.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "eigenvalues": [2.0, 1.0],
+                "eigenvectors": [[1.0, 0.0], [0.0, 1.0]],
+                "steps": 0,
+                "sweeps": 0,
+                "off_diagonal": 0.0,
+                "converged": true
+            })
+        );
     }
 }
@@ -1,33 +1,222 @@
-use ndarray::{ArrayBase, Ix2, OwnedRepr};
+use std::f64::consts::PI;
+
+use ndarray::{ArrayView2, OwnedRepr};
 use ndarray_linalg::{random_hermite, EigVals};
+use plotters::prelude::*;
 
-use eigenvalues_problem::{jacobi_method, Circles, MaxModule, OptimalElement};
+use eigenvalues_problem::{
+    cyclic_jacobi_method, cyclic_jacobi_method_observed, jacobi_method, jacobi_method_observed,
+    parallel_jacobi_method, parallel_jacobi_method_observed, Circles, JacobiStep, MaxModule,
+    OptimalElement,
+};
 use matrices::Examples;
 
 fn main() {
     let matrix = random_hermite::<f64, OwnedRepr<f64>>(3);
-    examine(matrix);
+    examine(matrix.view());
+    draw_discs(
+        matrix.view(),
+        "task_5_random.svg",
+        "Random symmetric matrix",
+    );
 
     let hilbert = Examples::hilbert(90);
-    examine(hilbert);
+    examine(hilbert.view());
+    draw_discs(hilbert.view(), "task_5_hilbert.svg", "Hilbert matrix");
+
+    let hilbert = Examples::hilbert(30);
+    draw_convergence(
+        hilbert.view(),
+        "task_6_convergence.svg",
+        "Hilbert matrix, n = 30",
+    );
 }
 
-fn examine(matrix: ArrayBase<OwnedRepr<f64>, Ix2>) {
+fn examine(matrix: ArrayView2<f64>) {
     let epsilons = vec![1e-5, 1e-7, 1e-9];
     for epsilon in epsilons {
         println!("{epsilon}");
         println!("MaxModule");
-        let eigenvalues = jacobi_method(matrix.view(), MaxModule, epsilon);
+        let eigenvalues = jacobi_method(matrix, MaxModule::new(matrix), epsilon);
         println!("{eigenvalues:.2}");
         println!("{:.2?}", matrix.eigvals());
-        let circles = Circles::new(matrix.view());
+        let circles = Circles::new(matrix);
         println!("{circles}");
 
         println!("OptimalElement");
-        let eigenvalues = jacobi_method(matrix.view(), OptimalElement::new(matrix.view()), epsilon);
+        let eigenvalues = jacobi_method(matrix, OptimalElement::new(matrix), epsilon);
         println!("{eigenvalues:.2}");
         println!("{:.2?}", matrix.eigvals());
-        let circles = Circles::new(matrix.view());
+        let circles = Circles::new(matrix);
         println!("{circles}");
+
+        println!("Cyclic threshold");
+        let eigenvalues = cyclic_jacobi_method(matrix, epsilon);
+        println!("{eigenvalues:.2}");
+
+        println!("Parallel round-robin");
+        let eigenvalues = parallel_jacobi_method(matrix, epsilon);
+        println!("{eigenvalues:.2}");
+    }
+}
+
+/// Row and column Gershgorin discs with the eigenvalues of the Jacobi method and of LAPACK.
+fn draw_discs(matrix: ArrayView2<f64>, path: &str, caption: &str) {
+    const SIZE: (u32, u32) = (600, 400);
+
+    let circles = Circles::new(matrix);
+    let jacobi = jacobi_method(matrix, MaxModule::new(matrix), 1e-9);
+    let lapack = matrix.eigvals().unwrap();
+
+    // equal scales on both axes, so that discs stay round
+    let bounds = circles.bounds();
+    let (left, right) = circles.rows().iter().chain(circles.columns()).fold(
+        (f64::INFINITY, f64::NEG_INFINITY),
+        |(left, right), disc| {
+            (
+                left.min(disc.center() - disc.radius()),
+                right.max(disc.center() + disc.radius()),
+            )
+        },
+    );
+    let margin = (right - left) * 0.05;
+    let (left, right) = (left - margin, right + margin);
+    let height = (right - left) * SIZE.1 as f64 / SIZE.0 as f64 / 2.0;
+    let height = height.max(bounds.imaginary);
+
+    let root_area = SVGBackend::new(path, SIZE).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 40)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(caption, ("sans-serif", 30))
+        .build_cartesian_2d(left..right, -height..height)
+        .unwrap();
+    ctx.configure_mesh()
+        .x_desc("Re")
+        .y_desc("Im")
+        .draw()
+        .unwrap();
+
+    for (discs, color, label) in [
+        (circles.rows(), BLUE, "row discs"),
+        (circles.columns(), RED, "column discs"),
+    ] {
+        ctx.draw_series(discs.iter().map(|disc| {
+            let points = (0..=100)
+                .map(|k| 2.0 * PI * k as f64 / 100.0)
+                .map(|angle| {
+                    (
+                        disc.center() + disc.radius() * angle.cos(),
+                        disc.radius() * angle.sin(),
+                    )
+                })
+                .collect::<Vec<_>>();
+            PathElement::new(points, color)
+        }))
+        .unwrap()
+        .label(label)
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
     }
+
+    ctx.draw_series(
+        jacobi
+            .eigenvalues()
+            .iter()
+            .map(|&lambda| Cross::new((lambda, 0.0), 6, GREEN.stroke_width(2))),
+    )
+    .unwrap()
+    .label("Jacobi")
+    .legend(|(x, y)| Cross::new((x + 10, y), 6, GREEN.stroke_width(2)));
+
+    ctx.draw_series(
+        lapack
+            .iter()
+            .map(|lambda| Circle::new((lambda.re, lambda.im), 3, BLACK.filled())),
+    )
+    .unwrap()
+    .label("eigvals")
+    .legend(|(x, y)| Circle::new((x + 10, y), 3, BLACK.filled()));
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
+}
+
+/// Off-diagonal norm against rotations for every strategy, on a logarithmic scale.
+fn draw_convergence(matrix: ArrayView2<f64>, path: &str, caption: &str) {
+    const SIZE: (u32, u32) = (600, 400);
+    const EPSILON: f64 = 1e-12;
+
+    let (mut max, mut optimal, mut cyclic, mut parallel) =
+        (Vec::new(), Vec::new(), Vec::new(), Vec::new());
+    jacobi_method_observed(
+        matrix,
+        MaxModule::new(matrix),
+        EPSILON,
+        &mut |step: JacobiStep| max.push((step.step, step.off_diagonal())),
+    );
+    jacobi_method_observed(
+        matrix,
+        OptimalElement::new(matrix),
+        EPSILON,
+        &mut |step: JacobiStep| optimal.push((step.step, step.off_diagonal())),
+    );
+    cyclic_jacobi_method_observed(matrix, EPSILON, &mut |step: JacobiStep| {
+        cyclic.push((step.step, step.off_diagonal()))
+    });
+    parallel_jacobi_method_observed(matrix, EPSILON, &mut |step: JacobiStep| {
+        parallel.push((step.step, step.off_diagonal()))
+    });
+    let traces = [
+        (max, BLUE, "max module"),
+        (optimal, RED, "optimal element"),
+        (cyclic, GREEN, "cyclic"),
+        (parallel, BLACK, "parallel"),
+    ];
+
+    // a norm of exactly zero has no place on a logarithmic axis
+    let points = traces
+        .iter()
+        .flat_map(|(trace, _, _)| trace)
+        .filter(|(_, norm)| *norm > 0.0);
+    let steps = points.clone().map(|&(step, _)| step).max().unwrap_or(1);
+    let (low, high) = points.fold((f64::INFINITY, 0.0_f64), |(low, high), &(_, norm)| {
+        (low.min(norm), high.max(norm))
+    });
+
+    let root_area = SVGBackend::new(path, SIZE).into_drawing_area();
+    root_area.fill(&WHITE).unwrap();
+
+    let mut ctx = ChartBuilder::on(&root_area)
+        .set_label_area_size(LabelAreaPosition::Left, 60)
+        .set_label_area_size(LabelAreaPosition::Bottom, 40)
+        .caption(caption, ("sans-serif", 30))
+        .build_cartesian_2d(0..steps, (low..high).log_scale())
+        .unwrap();
+    ctx.configure_mesh()
+        .x_desc("rotations")
+        .y_desc("off-diagonal norm")
+        .y_label_formatter(&|y| format!("{y:.0e}"))
+        .draw()
+        .unwrap();
+
+    for (trace, color, label) in traces {
+        ctx.draw_series(LineSeries::new(
+            trace.into_iter().filter(|(_, norm)| *norm > 0.0),
+            color,
+        ))
+        .unwrap()
+        .label(label)
+        .legend(move |(x, y)| PathElement::new(vec![(x, y), (x + 20, y)], color));
+    }
+
+    ctx.configure_series_labels()
+        .border_style(BLACK)
+        .background_style(WHITE.mix(0.8))
+        .draw()
+        .unwrap();
 }
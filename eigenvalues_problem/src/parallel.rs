@@ -0,0 +1,160 @@
+use ndarray::{Array2, ArrayView2, Axis};
+use rayon::prelude::*;
+
+use matrices::Observer;
+
+use crate::{
+    off_diagonal_norm, off_diagonal_sums, rotate_lanes, rotation, row_converged, JacobiResult,
+    JacobiStep, MAX_SWEEPS,
+};
+
+/// Plane `(i, j)` and `(cos, sin)` of a rotation.
+type PlaneRotation = ((usize, usize), (f64, f64));
+
+/// Round-robin (Brent–Luk) ordering: `n - 1` rounds, `n` for odd `n`,
+/// of disjoint pairs `i < j`, every pair appears once in a sweep.
+///
+/// Index `0` stays in place and the others move by one position each round,
+/// for odd `n` the index paired with the dummy `n` idles.
+pub fn round_robin(n: usize) -> Vec<Vec<(usize, usize)>> {
+    let m = n + n % 2;
+    let mut players = (0..m).collect::<Vec<_>>();
+    (1..m)
+        .map(|_| {
+            let round = (0..m / 2)
+                .map(|k| (players[k], players[m - 1 - k]))
+                .filter(|&(a, b)| a.max(b) < n)
+                .map(|(a, b)| (a.min(b), a.max(b)))
+                .collect();
+            players[1..].rotate_right(1);
+            round
+        })
+        .collect()
+}
+
+/// Jacobi method with the rotations of a [`round_robin`] round applied concurrently.
+///
+/// The rotations of a round touch disjoint rows and columns, so `A <- J A Jᵀ`
+/// for their product `J` annihilates every pair of the round at once.
+/// Convergence (`R_k = Σ_{l≠k} |a_kl| < epsilon` or `R_k = 0` for every row)
+/// is checked once per sweep.
+pub fn parallel_jacobi_method(matrix: ArrayView2<f64>, epsilon: f64) -> JacobiResult {
+    parallel_jacobi_method_observed(matrix, epsilon, &mut ())
+}
+
+/// [`parallel_jacobi_method`] reporting every round to `observer`.
+pub fn parallel_jacobi_method_observed<O>(
+    matrix: ArrayView2<f64>,
+    epsilon: f64,
+    observer: &mut O,
+) -> JacobiResult
+where
+    O: for<'a> Observer<JacobiStep<'a>>,
+{
+    let n = matrix.nrows();
+    let rounds = round_robin(n);
+
+    let mut matrix = matrix.to_owned();
+    let mut eigenvectors = Array2::eye(n);
+    let mut steps = 0;
+    let mut sweeps = 0;
+    let converged = loop {
+        if off_diagonal_sums(matrix.view())
+            .iter()
+            .all(|&sum| row_converged(sum, epsilon))
+        {
+            break true;
+        }
+        if sweeps == MAX_SWEEPS {
+            break false;
+        }
+        sweeps += 1;
+
+        for round in &rounds {
+            let rotations = round
+                .iter()
+                .filter(|&&pos| matrix[pos] != 0.0)
+                .map(|&pos| (pos, rotation(pos, matrix.view())))
+                .collect::<Vec<_>>();
+            rotate_pairs(&mut matrix, Axis(0), &rotations);
+            rotate_pairs(&mut matrix, Axis(1), &rotations);
+            rotate_pairs(&mut eigenvectors, Axis(1), &rotations);
+            steps += rotations.len() as u32;
+            observer.observe(JacobiStep {
+                step: steps,
+                matrix: matrix.view(),
+            });
+        }
+    };
+
+    JacobiResult {
+        eigenvalues: (0..n).map(|i| matrix[(i, i)]).collect(),
+        eigenvectors,
+        steps,
+        sweeps,
+        off_diagonal: off_diagonal_norm(matrix.view()),
+        converged,
+    }
+}
+
+/// Rotates the lanes `i` and `j` along `axis` for every rotation, the pairs must be disjoint.
+fn rotate_pairs(matrix: &mut Array2<f64>, axis: Axis, rotations: &[PlaneRotation]) {
+    let mut lanes = matrix.axis_iter_mut(axis).map(Some).collect::<Vec<_>>();
+    let pairs = rotations
+        .iter()
+        .map(|&((i, j), rotation)| (lanes[i].take().unwrap(), lanes[j].take().unwrap(), rotation))
+        .collect::<Vec<_>>();
+    pairs
+        .into_par_iter()
+        .for_each(|(a, b, rotation)| rotate_lanes(a, b, rotation));
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use matrices::Examples;
+
+    use crate::{jacobi_method, parallel_jacobi_method, round_robin, MaxModule};
+
+    #[test]
+    fn round_robin_covers_every_pair_once() {
+        for n in [2, 7, 8] {
+            let mut pairs = HashSet::new();
+            for round in round_robin(n) {
+                let mut indices = HashSet::new();
+                for (i, j) in round {
+                    assert!(i < j && j < n);
+                    assert!(indices.insert(i) && indices.insert(j));
+                    assert!(pairs.insert((i, j)));
+                }
+            }
+            assert_eq!(pairs.len(), n * (n - 1) / 2);
+        }
+    }
+
+    #[test]
+    fn parallel_jacobi_matches_pivoting() {
+        for n in [9, 12] {
+            let matrix = Examples::hilbert(n);
+            let pivoting = jacobi_method(matrix.view(), MaxModule::new(matrix.view()), 1e-12);
+            let parallel = parallel_jacobi_method(matrix.view(), 1e-12);
+
+            let mut expected = pivoting.eigenvalues.clone();
+            let mut eigenvalues = parallel.eigenvalues.clone();
+            expected.sort_by(f64::total_cmp);
+            eigenvalues.sort_by(f64::total_cmp);
+            for (a, b) in eigenvalues.iter().zip(expected.iter()) {
+                assert!((a - b).abs() < 1e-12);
+            }
+
+            let vectors = parallel.eigenvectors();
+            let product = matrix.dot(&vectors);
+            for (k, lambda) in parallel.eigenvalues.iter().enumerate() {
+                let residual = &product.column(k) - &(&vectors.column(k) * *lambda);
+                assert!(residual.iter().all(|r| r.abs() < 1e-11));
+            }
+            assert!(parallel.sweeps() < 10);
+        }
+    }
+}
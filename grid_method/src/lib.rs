@@ -22,13 +22,36 @@ pub struct Equation {
 }
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Solution {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     pub u: Array1<f64>,
     pub grid: Vec<f64>,
     pub errors: Vec<f64>,
     pub grid_sizes: Vec<usize>,
 }
 
+/// Row `x,u` of the CSV table of a [`Solution`] on its final grid.
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize)]
+pub struct GridRecord {
+    pub x: f64,
+    pub u: f64,
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for Solution {
+    type Record = GridRecord;
+
+    fn records(&self) -> Vec<GridRecord> {
+        self.grid
+            .iter()
+            .zip(self.u.iter())
+            .map(|(&x, &u)| GridRecord { x, u })
+            .collect()
+    }
+}
+
 pub fn solve_grid(equation: Equation, mut n: usize, epsilon: f64) -> Solution {
     let mut errors = Vec::new();
     let mut grid_sizes = vec![n];
@@ -112,3 +135,39 @@ fn generate_matrix(equation: &Equation, n: usize) -> (Array2<f64>, Array1<f64>)
 fn generate_grid((a, b): (f64, f64), n: usize) -> Vec<f64> {
     Array1::linspace(a, b, n + 1).to_vec()
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ndarray::array;
+
+    use matrices::export::Export;
+
+    use crate::Solution;
+
+    #[test]
+    fn solution_to_json_and_csv() {
+        let solution = Solution {
+            u: array![0.0, 0.5],
+            grid: vec![0.0, 1.0],
+            errors: vec![1e-3],
+            grid_sizes: vec![1, 2],
+        };
+
+        let mut json = Vec::new();
+        solution.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "u": [0.0, 0.5],
+                "grid": [0.0, 1.0],
+                "errors": [1e-3],
+                "grid_sizes": [1, 2]
+            })
+        );
+
+        let mut csv = Vec::new();
+        solution.write_csv(&mut csv).unwrap();
+        assert_eq!(String::from_utf8(csv).unwrap(), "x,u\n0.0,0.0\n1.0,0.5\n");
+    }
+}
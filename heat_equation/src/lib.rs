@@ -18,12 +18,42 @@ pub struct Equation {
     pub mu2: fn(f64) -> f64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Solution {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     pub x_grid: Array1<f64>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     pub t_grid: Array1<f64>,
+    /// Rows are time layers.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::matrix"))]
     pub approximate_solution: Array2<f64>,
 }
 
+/// Row `t,x,u` of the CSV table of a [`Solution`], one per grid node.
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize)]
+pub struct NodeRecord {
+    pub t: f64,
+    pub x: f64,
+    pub u: f64,
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for Solution {
+    type Record = NodeRecord;
+
+    fn records(&self) -> Vec<NodeRecord> {
+        self.approximate_solution
+            .indexed_iter()
+            .map(|((j, i), &u)| NodeRecord {
+                t: self.t_grid[j],
+                x: self.x_grid[i],
+                u,
+            })
+            .collect()
+    }
+}
+
 pub fn solve_explicit(equation: &Equation, n: usize, m: usize) -> Solution {
     let x_grid = Array1::linspace(0.0, equation.a, n + 1);
     let t_grid = Array1::linspace(0.0, equation.T, m + 1);
@@ -117,3 +147,40 @@ fn local_matrix(equation: &Equation, n: usize, m: usize) -> Array2<f64> {
 
     matrix
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ndarray::array;
+
+    use matrices::export::Export;
+
+    use crate::Solution;
+
+    #[test]
+    fn solution_to_json_and_csv() {
+        let solution = Solution {
+            x_grid: array![0.0, 1.0],
+            t_grid: array![0.0],
+            approximate_solution: array![[0.5, 1.5]],
+        };
+
+        let mut json = Vec::new();
+        solution.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "x_grid": [0.0, 1.0],
+                "t_grid": [0.0],
+                "approximate_solution": [[0.5, 1.5]]
+            })
+        );
+
+        let mut csv = Vec::new();
+        solution.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "t,x,u\n0.0,0.0,0.5\n0.0,1.0,1.5\n"
+        );
+    }
+}
@@ -2,7 +2,9 @@ use ndarray::{Array1, Array2, ArrayView1, ArrayView2, ShapeBuilder};
 use ndarray_linalg::Norm;
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct IterationResult {
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     x: Array1<f64>,
     iteration_count: u32,
 }
@@ -17,6 +19,15 @@ impl IterationResult {
     }
 }
 
+#[cfg(feature = "serde")]
+impl matrices::export::Export for IterationResult {
+    type Record = matrices::export::ComponentRecord;
+
+    fn records(&self) -> Vec<Self::Record> {
+        matrices::export::components(&self.x)
+    }
+}
+
 pub fn iterate(matrix: ArrayView2<f64>, vector: ArrayView1<f64>, epsilon: f64) -> IterationResult {
     let n = matrix.nrows();
 
@@ -90,3 +101,28 @@ fn iterative_matrices(
     let c = (0..n).map(|i| vector[i] / matrix[(i, i)]).collect();
     (b, c)
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ndarray::array;
+
+    use matrices::export::Export;
+
+    use crate::IterationResult;
+
+    #[test]
+    fn iteration_result_to_json() {
+        let result = IterationResult {
+            x: array![1.0, -0.5],
+            iteration_count: 3,
+        };
+
+        let mut json = Vec::new();
+        result.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"x": [1.0, -0.5], "iteration_count": 3})
+        );
+    }
+}
@@ -35,6 +35,19 @@ impl Examples {
         })
     }
 
+    /// Tridiagonal `[-1, 2, -1]` matrix with known eigenvalues `2 - 2cos(kπ / (n + 1))`.
+    pub fn second_difference(n: usize) -> Array2<f64> {
+        generate_matrix(n, |row, column| {
+            if row == column {
+                2.0
+            } else if row.abs_diff(column) == 1 {
+                -1.0
+            } else {
+                0.0
+            }
+        })
+    }
+
     pub fn diagonal(n: usize) -> Array2<f64> {
         generate_matrix(n, |row, column| {
             if row == column {
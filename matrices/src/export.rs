@@ -0,0 +1,171 @@
+//! CSV and JSON writers for the results of the other crates.
+//!
+//! Arrays are written as plain (nested) lists rather than in the `ndarray` serde layout.
+
+use std::error::Error;
+use std::fmt::{Display, Formatter};
+use std::io::Write;
+
+use ndarray::{Array1, Array2};
+use serde::ser::SerializeSeq;
+use serde::{Serialize, Serializer};
+
+/// A result that can be written as a JSON document and as a CSV table of `Record`s.
+pub trait Export: Serialize {
+    type Record: Serialize;
+
+    /// Rows of the CSV table.
+    fn records(&self) -> Vec<Self::Record>;
+
+    fn write_json<W: Write>(&self, writer: W) -> Result<(), ExportError> {
+        write_json(writer, self)
+    }
+
+    fn write_csv<W: Write>(&self, writer: W) -> Result<(), ExportError> {
+        write_csv(writer, self.records())
+    }
+}
+
+#[derive(Debug)]
+pub enum ExportError {
+    Json(serde_json::Error),
+    Csv(csv::Error),
+}
+
+impl Display for ExportError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ExportError::Json(error) => write!(f, "JSON export failed: {error}"),
+            ExportError::Csv(error) => write!(f, "CSV export failed: {error}"),
+        }
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::Json(error) => Some(error),
+            ExportError::Csv(error) => Some(error),
+        }
+    }
+}
+
+impl From<serde_json::Error> for ExportError {
+    fn from(error: serde_json::Error) -> Self {
+        ExportError::Json(error)
+    }
+}
+
+impl From<csv::Error> for ExportError {
+    fn from(error: csv::Error) -> Self {
+        ExportError::Csv(error)
+    }
+}
+
+pub fn write_json<W: Write, T: Serialize + ?Sized>(
+    writer: W,
+    value: &T,
+) -> Result<(), ExportError> {
+    serde_json::to_writer_pretty(writer, value)?;
+    Ok(())
+}
+
+/// Header from the field names of the first record.
+pub fn write_csv<W, R, I>(writer: W, records: I) -> Result<(), ExportError>
+where
+    W: Write,
+    R: Serialize,
+    I: IntoIterator<Item = R>,
+{
+    let mut writer = csv::Writer::from_writer(writer);
+    for record in records {
+        writer.serialize(record)?;
+    }
+    writer.flush().map_err(csv::Error::from)?;
+    Ok(())
+}
+
+/// CSV row of a vector-valued result.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ComponentRecord {
+    pub index: usize,
+    pub value: f64,
+}
+
+pub fn components<'a, I: IntoIterator<Item = &'a f64>>(values: I) -> Vec<ComponentRecord> {
+    values
+        .into_iter()
+        .enumerate()
+        .map(|(index, &value)| ComponentRecord { index, value })
+        .collect()
+}
+
+/// `serialize_with` for an [`Array1`] as a list.
+pub fn vector<S: Serializer>(vector: &Array1<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(vector.iter())
+}
+
+/// `serialize_with` for an [`Array2`] as a list of rows.
+pub fn matrix<S: Serializer>(matrix: &Array2<f64>, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_seq(matrix.rows().into_iter().map(|row| row.to_vec()))
+}
+
+/// `serialize_with` for a list of [`Array1`].
+pub fn vectors<S: Serializer>(vectors: &[Array1<f64>], serializer: S) -> Result<S::Ok, S::Error> {
+    let mut seq = serializer.serialize_seq(Some(vectors.len()))?;
+    for vector in vectors {
+        seq.serialize_element(&vector.to_vec())?;
+    }
+    seq.end()
+}
+
+#[cfg(test)]
+mod tests {
+    use ndarray::{array, Array1, Array2};
+    use serde::Serialize;
+
+    use crate::export::{components, write_csv, write_json, ComponentRecord, Export};
+
+    #[derive(Serialize)]
+    struct Result {
+        #[serde(serialize_with = "crate::export::vector")]
+        x: Array1<f64>,
+        #[serde(serialize_with = "crate::export::matrix")]
+        a: Array2<f64>,
+    }
+
+    impl Export for Result {
+        type Record = ComponentRecord;
+
+        fn records(&self) -> Vec<ComponentRecord> {
+            components(&self.x)
+        }
+    }
+
+    #[test]
+    fn arrays_are_written_as_lists() {
+        let result = Result {
+            x: array![1.0, 2.5],
+            a: array![[1.0, 2.0], [3.0, 4.0]],
+        };
+
+        let mut json = Vec::new();
+        write_json(&mut json, &result).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({"x": [1.0, 2.5], "a": [[1.0, 2.0], [3.0, 4.0]]})
+        );
+
+        let mut csv = Vec::new();
+        result.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "index,value\n0,1.0\n1,2.5\n"
+        );
+
+        let mut empty = Vec::new();
+        write_csv(&mut empty, Vec::<ComponentRecord>::new()).unwrap();
+        assert!(empty.is_empty());
+    }
+}
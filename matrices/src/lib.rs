@@ -1,8 +1,14 @@
 use ndarray::Array2;
 
 pub use examples::*;
+pub use observer::*;
+pub use report::*;
 
 mod examples;
+#[cfg(feature = "serde")]
+pub mod export;
+mod observer;
+mod report;
 
 pub fn generate_matrix<T, F: Fn(usize, usize) -> T>(n: usize, generator: F) -> Array2<T> {
     let matrix = (0..n)
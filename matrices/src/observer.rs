@@ -0,0 +1,24 @@
+/// Receives a record after every iteration of a method.
+///
+/// `()` ignores the records, `Vec<T>` collects a trace and closures `FnMut(T)` do anything else.
+/// A `Vec<T>` cannot collect records that borrow the state of the method,
+/// such records provide an owned copy to collect instead.
+pub trait Observer<T> {
+    fn observe(&mut self, record: T);
+}
+
+impl<T> Observer<T> for () {
+    fn observe(&mut self, _: T) {}
+}
+
+impl<T> Observer<T> for Vec<T> {
+    fn observe(&mut self, record: T) {
+        self.push(record);
+    }
+}
+
+impl<T, F: FnMut(T)> Observer<T> for F {
+    fn observe(&mut self, record: T) {
+        self(record)
+    }
+}
@@ -0,0 +1,216 @@
+use prettytable::{Cell, Row, Table};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum Language {
+    #[default]
+    Russian,
+    English,
+}
+
+impl Language {
+    pub fn pick<'a>(&self, russian: &'a str, english: &'a str) -> &'a str {
+        match self {
+            Language::Russian => russian,
+            Language::English => english,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub enum TableFormat {
+    /// Plain text table of `prettytable`.
+    #[default]
+    Pretty,
+    /// GitHub flavoured Markdown.
+    Markdown,
+    /// `tabular` environment.
+    Latex,
+}
+
+/// How results are written for a human reader.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct ReportStyle {
+    pub language: Language,
+    /// Digits after the decimal point.
+    pub precision: usize,
+    pub format: TableFormat,
+}
+
+impl Default for ReportStyle {
+    fn default() -> Self {
+        Self {
+            language: Language::Russian,
+            precision: 3,
+            format: TableFormat::Pretty,
+        }
+    }
+}
+
+impl ReportStyle {
+    pub fn text<'a>(&self, russian: &'a str, english: &'a str) -> &'a str {
+        self.language.pick(russian, english)
+    }
+
+    pub fn number(&self, value: f64) -> String {
+        format!("{value:.*}", self.precision)
+    }
+
+    pub fn scientific(&self, value: f64) -> String {
+        format!("{value:.*e}", self.precision)
+    }
+}
+
+/// Table independent of the output format.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReportTable {
+    header: Vec<String>,
+    rows: Vec<Vec<String>>,
+}
+
+impl ReportTable {
+    pub fn new<S: ToString>(header: &[S]) -> Self {
+        Self {
+            header: header.iter().map(|cell| cell.to_string()).collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    pub fn add_row(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    pub fn header(&self) -> &[String] {
+        &self.header
+    }
+
+    pub fn rows(&self) -> &[Vec<String>] {
+        &self.rows
+    }
+
+    pub fn render(&self, format: TableFormat) -> String {
+        match format {
+            TableFormat::Pretty => self.pretty().to_string(),
+            TableFormat::Markdown => self.markdown(),
+            TableFormat::Latex => self.latex(),
+        }
+    }
+
+    pub fn pretty(&self) -> Table {
+        let mut table = Table::new();
+        for row in std::iter::once(&self.header).chain(self.rows.iter()) {
+            table.add_row(pretty_row(row));
+        }
+        table
+    }
+
+    /// Body row `index` for a `prettytable` table assembled elsewhere.
+    pub fn pretty_row(&self, index: usize) -> Row {
+        pretty_row(&self.rows[index])
+    }
+
+    pub fn markdown(&self) -> String {
+        let line = |row: &[String]| {
+            let cells = row
+                .iter()
+                .map(|cell| cell.replace('|', "\\|"))
+                .collect::<Vec<_>>();
+            format!("| {} |\n", cells.join(" | "))
+        };
+
+        let mut markdown = line(&self.header);
+        markdown += &format!("|{}\n", " --- |".repeat(self.header.len()));
+        for row in &self.rows {
+            markdown += &line(row);
+        }
+        markdown
+    }
+
+    pub fn latex(&self) -> String {
+        let line = |row: &[String]| {
+            let cells = row
+                .iter()
+                .map(|cell| latex_escape(cell))
+                .collect::<Vec<_>>();
+            format!("{} \\\\\n", cells.join(" & "))
+        };
+
+        let mut latex = format!("\\begin{{tabular}}{{{}}}\n", "c".repeat(self.header.len()));
+        latex += "\\hline\n";
+        latex += &line(&self.header);
+        latex += "\\hline\n";
+        for row in &self.rows {
+            latex += &line(row);
+        }
+        latex += "\\hline\n\\end{tabular}\n";
+        latex
+    }
+}
+
+fn latex_escape(cell: &str) -> String {
+    cell.chars()
+        .map(|c| match c {
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{c}"),
+            '\\' => "\\textbackslash{}".to_string(),
+            '~' => "\\textasciitilde{}".to_string(),
+            '^' => "\\textasciicircum{}".to_string(),
+            c => c.to_string(),
+        })
+        .collect()
+}
+
+/// Result that can be presented in several languages and table formats.
+pub trait Report {
+    fn report_table(&self, style: &ReportStyle) -> ReportTable;
+
+    /// Text description, one quantity per line.
+    fn describe(&self, style: &ReportStyle) -> String;
+
+    fn render(&self, style: &ReportStyle) -> String {
+        self.report_table(style).render(style.format)
+    }
+}
+
+fn pretty_row(cells: &[String]) -> Row {
+    Row::new(cells.iter().map(|cell| Cell::new(cell)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::report::{ReportStyle, ReportTable};
+
+    fn table() -> ReportTable {
+        let style = ReportStyle {
+            precision: 2,
+            ..ReportStyle::default()
+        };
+        let mut table = ReportTable::new(&["norm", "cond_2"]);
+        table.add_row(vec!["F".to_string(), style.number(12.3456)]);
+        table
+    }
+
+    #[test]
+    fn markdown_table() {
+        assert_eq!(
+            table().markdown(),
+            "| norm | cond_2 |\n| --- | --- |\n| F | 12.35 |\n"
+        );
+    }
+
+    #[test]
+    fn latex_table() {
+        assert_eq!(
+            table().latex(),
+            "\\begin{tabular}{cc}\n\\hline\nnorm & cond\\_2 \\\\\n\\hline\nF & 12.35 \\\\\n\\hline\n\\end{tabular}\n"
+        );
+    }
+
+    #[test]
+    fn latex_escapes_special_characters() {
+        let mut table = ReportTable::new(&["a\\b", "~x^2"]);
+        table.add_row(vec!["50%".to_string(), "{1}".to_string()]);
+        assert_eq!(
+            table.latex(),
+            "\\begin{tabular}{cc}\n\\hline\na\\textbackslash{}b & \\textasciitilde{}x\\textasciicircum{}2 \\\\\n\\hline\n50\\% & \\{1\\} \\\\\n\\hline\n\\end{tabular}\n"
+        );
+    }
+}
@@ -3,6 +3,7 @@ use ndarray::Array1;
 use ndarray_linalg::{Inverse, Norm};
 
 #[derive(Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub enum Method {
     Gradient,
     HeavyBall,
@@ -17,13 +18,46 @@ pub struct Task {
     pub beta: f64,
 }
 
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
 pub struct Answer {
     pub method: Method,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vector"))]
     pub min: Array1<f64>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "matrices::export::vectors"))]
     pub points: Vec<Array1<f64>>,
     pub steps: u32,
 }
 
+/// Row `point,coordinate,value` of the CSV table of the [`Answer`] trajectory.
+#[cfg(feature = "serde")]
+#[derive(Debug, serde::Serialize)]
+pub struct PointRecord {
+    pub point: usize,
+    pub coordinate: usize,
+    pub value: f64,
+}
+
+#[cfg(feature = "serde")]
+impl matrices::export::Export for Answer {
+    type Record = PointRecord;
+
+    fn records(&self) -> Vec<PointRecord> {
+        self.points
+            .iter()
+            .enumerate()
+            .flat_map(|(point, x)| {
+                x.iter()
+                    .enumerate()
+                    .map(move |(coordinate, &value)| PointRecord {
+                        point,
+                        coordinate,
+                        value,
+                    })
+            })
+            .collect()
+    }
+}
+
 pub fn minimize_gradient(task: &Task, epsilon: f64) -> Answer {
     let mut x = task.start_point.clone();
     let mut steps = 0;
@@ -126,3 +160,42 @@ pub fn minimize_newton(task: &Task, epsilon: f64) -> Answer {
         steps,
     }
 }
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use ndarray::array;
+
+    use matrices::export::Export;
+
+    use crate::{Answer, Method};
+
+    #[test]
+    fn answer_to_json_and_csv() {
+        let answer = Answer {
+            method: Method::Newton,
+            min: array![1.0, 2.0],
+            points: vec![array![0.0, 0.0], array![1.0, 2.0]],
+            steps: 1,
+        };
+
+        let mut json = Vec::new();
+        answer.write_json(&mut json).unwrap();
+        let json: serde_json::Value = serde_json::from_slice(&json).unwrap();
+        assert_eq!(
+            json,
+            serde_json::json!({
+                "method": "Newton",
+                "min": [1.0, 2.0],
+                "points": [[0.0, 0.0], [1.0, 2.0]],
+                "steps": 1
+            })
+        );
+
+        let mut csv = Vec::new();
+        answer.write_csv(&mut csv).unwrap();
+        assert_eq!(
+            String::from_utf8(csv).unwrap(),
+            "point,coordinate,value\n0,0,0.0\n0,1,0.0\n1,0,1.0\n1,1,2.0\n"
+        );
+    }
+}